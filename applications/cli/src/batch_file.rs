@@ -0,0 +1,163 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Reads the user lists for batch compliance operations.
+//!
+//! Files ending in `.json` contain either an array of user IDs (`[1, 2, 3]`) or an array of
+//! `{"user_id": 1, "amount": 100}` objects. Any other file is read as CSV with one `user_id` or `user_id,amount` entry
+//! per line. Blank lines and `#` comments are ignored, as is a header line whose first column is `user_id`.
+
+use crate::value_parsers;
+use anyhow::{anyhow, Context};
+use std::fs;
+use std::path::Path;
+use tari_template_lib::models::Amount;
+
+pub type UserId = u64;
+
+#[derive(serde::Deserialize)]
+struct UserAmountEntry {
+    user_id: UserId,
+    amount: u128,
+}
+
+pub fn read_user_ids(path: &Path) -> anyhow::Result<Vec<UserId>> {
+    let contents = read_file(path)?;
+    if is_json(path) {
+        return serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid user ID list in {}", path.display()));
+    }
+
+    csv_records(&contents)
+        .map(|(line_no, fields)| match fields.as_slice() {
+            [user_id] => parse_user_id(user_id, line_no),
+            _ => Err(anyhow!("Line {}: expected a single user ID", line_no)),
+        })
+        .collect()
+}
+
+pub fn read_user_amounts(path: &Path) -> anyhow::Result<Vec<(UserId, Amount)>> {
+    let contents = read_file(path)?;
+    if is_json(path) {
+        let entries: Vec<UserAmountEntry> = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid user amount list in {}", path.display()))?;
+        return Ok(entries
+            .into_iter()
+            .map(|entry| (entry.user_id, Amount::new(entry.amount)))
+            .collect());
+    }
+
+    csv_records(&contents)
+        .map(|(line_no, fields)| match fields.as_slice() {
+            [user_id, amount] => {
+                let amount = value_parsers::amount(amount)
+                    .with_context(|| format!("Line {}: invalid amount '{}'", line_no, amount))?;
+                Ok((parse_user_id(user_id, line_no)?, amount))
+            }
+            _ => Err(anyhow!("Line {}: expected user_id,amount", line_no)),
+        })
+        .collect()
+}
+
+fn read_file(path: &Path) -> anyhow::Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// Returns the non-empty CSV lines with their 1-based line numbers, skipping comments and a `user_id` header line. Any
+/// other first line is read as an entry, so a mistyped first user ID is reported rather than dropped.
+fn csv_records(contents: &str) -> impl Iterator<Item = (usize, Vec<&str>)> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_no, line)| (line_no, line.split(',').map(str::trim).collect::<Vec<_>>()))
+        .enumerate()
+        .filter(|(i, (_, fields))| *i > 0 || !fields[0].eq_ignore_ascii_case("user_id"))
+        .map(|(_, record)| record)
+}
+
+fn parse_user_id(s: &str, line_no: usize) -> anyhow::Result<UserId> {
+    s.parse()
+        .with_context(|| format!("Line {}: invalid user ID '{}'", line_no, s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_file(name: &str, contents: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("stable_coin_cli_{}_{name}", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn it_skips_a_csv_header_line() {
+        let path = test_file("header.csv", "user_id,amount\n1,100\n2,200\n");
+        let entries = read_user_amounts(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(entries, vec![(1, Amount::new(100)), (2, Amount::new(200))]);
+    }
+
+    #[test]
+    fn it_reads_a_headerless_first_line_as_an_entry() {
+        let path = test_file("headerless.csv", "1\n2\n");
+        let user_ids = read_user_ids(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(user_ids, vec![1, 2]);
+
+        // A mistyped first user ID is reported rather than dropped as a header
+        let path = test_file("mistyped.csv", "1x\n2\n");
+        let err = read_user_ids(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(err.to_string(), "Line 1: invalid user ID '1x'");
+    }
+
+    #[test]
+    fn it_skips_blank_lines_and_comments() {
+        let path = test_file("blank.csv", "\n# users to freeze\nuser_id\n\n  3  \n\n4\n");
+        let user_ids = read_user_ids(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(user_ids, vec![3, 4]);
+
+        // Line numbers in errors count the skipped lines
+        let path = test_file("blank_error.csv", "user_id,amount\n\n5,100\n6\n");
+        let err = read_user_amounts(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(err.to_string(), "Line 4: expected user_id,amount");
+    }
+
+    #[test]
+    fn it_reads_json_batches_including_unknown_users() {
+        // Whether a user exists is checked by the issuer when the batch is submitted, so an unknown user ID is passed
+        // through for the issuer to report
+        let path = test_file("users.json", "[1, 999999]");
+        let user_ids = read_user_ids(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(user_ids, vec![1, 999999]);
+
+        let path = test_file(
+            "amounts.json",
+            r#"[{"user_id": 1, "amount": 100}, {"user_id": 999999, "amount": 5}]"#,
+        );
+        let entries = read_user_amounts(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            entries,
+            vec![(1, Amount::new(100)), (999999, Amount::new(5))]
+        );
+
+        let path = test_file("invalid.json", r#"[{"user": 1, "amount": 100}]"#);
+        let err = read_user_amounts(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(err.to_string().starts_with("Invalid user amount list in "));
+    }
+}
@@ -1,14 +1,26 @@
 // Copyright 2024 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
+use crate::batch_file::UserId;
+use crate::config::{self, Profile};
 use crate::context::CliContext;
-use crate::print_result::print_result;
-use crate::transactions::StableCoinTransaction;
-use crate::{transactions, value_parsers};
+use crate::print_result::{print_dry_run_result, print_result};
+use crate::transactions::{
+    BasisPointFee, FeeSpec, FeeTarget, FeeTier, IssuerAccess, RoundingMode, StableCoinTransaction,
+};
+use crate::{batch_file, transaction_file, transactions, value_parsers, view_key};
+use anyhow::Context;
 use clap::ArgAction;
 use clap::Parser;
-use tari_template_lib::crypto::RistrettoPublicKeyBytes;
-use tari_template_lib::models::{Amount, ComponentAddress, Metadata, TemplateAddress};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tari_engine_types::commit_result::TransactionResult;
+use tari_template_lib::crypto::{RistrettoPublicKeyBytes, StealthValueProof};
+use tari_template_lib::models::{
+    Amount, ComponentAddress, Metadata, ResourceAddress, TemplateAddress,
+};
+use tari_template_lib::prelude::{MaxString, UtxoId};
 use url::Url;
 
 #[derive(Clone, Debug, clap::Parser)]
@@ -27,41 +39,286 @@ impl Cli {
 
 #[derive(Clone, Debug, clap::Parser)]
 pub struct CommonCli {
+    /// The TOML file holding the CLI profiles
     #[clap(
-        short = 'u',
+        short = 'c',
         long,
-        default_value = "http://localhost:9000",
-        env = "WALLET_URL"
+        default_value = "stable_coin_cli.toml",
+        env = "STABLE_COIN_CONFIG"
     )]
-    pub wallet_url: Url,
+    pub config: PathBuf,
+    /// The profile to use. Defaults to the config file's active-profile, or "default".
+    #[clap(short = 'p', long, env = "STABLE_COIN_PROFILE")]
+    pub profile: Option<String>,
+    /// The wallet daemon URL. Defaults to the profile's wallet-url, or http://localhost:9000.
+    #[clap(short = 'u', long, env = "WALLET_URL")]
+    pub wallet_url: Option<Url>,
     #[clap(short = 'a', long, env = "FEE_ACCOUNT")]
     pub fee_account: Option<ComponentAddress>,
     #[clap(short = 't', long, env = "ISSUER_TEMPLATE")]
-    pub issuer_template: TemplateAddress,
+    pub issuer_template: Option<TemplateAddress>,
     #[clap(short = 'f', long, value_parser = value_parsers::amount, default_value = "2000")]
     pub max_fee: Amount,
+    /// Execute the transaction against the current state and print the would-be result and fee, without submitting it
+    #[clap(long)]
+    pub dry_run: bool,
+    /// Write the unsigned transaction to this file instead of submitting it, e.g. for signing on an offline machine.
+    /// Files ending in .cbor are written as CBOR, anything else as JSON.
+    #[clap(long, value_name = "FILE", conflicts_with = "dry_run")]
+    pub export: Option<PathBuf>,
 }
 
 #[derive(Clone, Debug, clap::Subcommand)]
 pub enum Command {
     #[clap(subcommand)]
     Issuer(IssuerSubcommand),
+    /// Sign a transaction exported with --export. Needs no wallet daemon, so it can run on an offline machine.
+    Sign(SignSubcommand),
+    /// Submit a signed transaction read from a JSON or CBOR file and wait for the result
+    Submit(SubmitSubcommand),
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct SignSubcommand {
+    /// An unsigned transaction. Files ending in .cbor are read as CBOR, anything else as JSON.
+    pub file: PathBuf,
+    /// A file containing the hex encoded secret key of the fee account's owner
+    #[clap(long, short = 'k')]
+    pub key_file: PathBuf,
+    /// The file to write the signed transaction to. Files ending in .cbor are written as CBOR, anything else as JSON.
+    #[clap(long, short)]
+    pub output: PathBuf,
+}
+
+impl SignSubcommand {
+    pub fn run(self, context: &CliContext) -> anyhow::Result<()> {
+        reject_transaction_output_flags(context, "sign")?;
+        let transaction = transaction_file::read_unsigned_transaction(&self.file)?;
+        let secret_key = transaction_file::read_signing_key(&self.key_file)?;
+        let transaction = transaction.finish().seal(&secret_key);
+        transaction_file::write_signed_transaction(&self.output, &transaction)?;
+        println!("Signed transaction written to {}", self.output.display());
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct SubmitSubcommand {
+    /// A signed transaction. Files ending in .cbor are read as CBOR, anything else as JSON.
+    pub file: PathBuf,
+}
+
+impl SubmitSubcommand {
+    pub async fn run(self, mut context: CliContext) -> anyhow::Result<()> {
+        reject_transaction_output_flags(&context, "submit")?;
+        let transaction = transaction_file::read_signed_transaction(&self.file)?;
+        let result = context
+            .submit_and_wait_signed_transaction(transaction)
+            .await?;
+        print_result(&result);
+
+        Ok(())
+    }
+}
+
+/// `sign` and `submit` work on an already built transaction, so the global --dry-run and --export flags do not apply
+fn reject_transaction_output_flags(context: &CliContext, command: &str) -> anyhow::Result<()> {
+    if context.is_dry_run() || context.export_path().is_some() {
+        anyhow::bail!("--dry-run and --export cannot be used with {command}");
+    }
+    Ok(())
 }
 
 #[derive(Clone, Debug, clap::Subcommand)]
 pub enum IssuerSubcommand {
     Create(IssuerCreateSubcommand),
+    /// Blacklist every user listed in a CSV or JSON file
+    BatchBlacklist(IssuerBatchBlacklistSubcommand),
+    /// Recall a revealed amount from every user listed in a CSV or JSON file
+    BatchRecall(IssuerBatchUserAmountsSubcommand),
+    /// Set the exchange limit of every user listed in a CSV or JSON file
+    BatchSetExchangeLimit(IssuerBatchUserAmountsSubcommand),
+    /// Mint new tokens into the issuer vault
+    IncreaseSupply(IssuerAmountSubcommand),
+    /// Burn tokens from the issuer vault
+    DecreaseSupply(IssuerAmountSubcommand),
+    /// Withdraw tokens from the issuer vault into an account
+    Withdraw(IssuerWithdrawSubcommand),
+    /// Deposit tokens held by the fee account into the issuer vault
+    Deposit(IssuerDepositSubcommand),
+    /// Create a user and send their badge to the user's account
+    CreateUser(IssuerUserAccountSubcommand),
+    /// Create an admin and send their badge to an account
+    CreateAdmin(IssuerCreateAdminSubcommand),
+    /// Blacklist a user, optionally recalling their revealed balance and freezing their UTXOs
+    Blacklist(IssuerBlacklistSubcommand),
+    /// Remove a user from the blacklist and return their badge to the user's account
+    Unblacklist(IssuerUserAccountSubcommand),
+    /// Set the exchange limit of a user
+    SetLimit(IssuerUserAmountSubcommand),
+    /// Set the transfer or wrapped exchange fee
+    SetFee(IssuerSetFeeSubcommand),
+    /// Pause all token transfers
+    Pause(IssuerArgs),
+    /// Resume token transfers
+    Unpause(IssuerArgs),
+    /// Freeze UTXOs so they cannot be spent
+    FreezeUtxos(IssuerUtxosSubcommand),
+    /// Unfreeze previously frozen UTXOs
+    UnfreezeUtxos(IssuerUtxosSubcommand),
+    /// Recall a revealed amount from a user's account
+    Recall(IssuerUserAmountSubcommand),
+    /// Burn a UTXO, reducing the total supply by its value
+    BurnUtxo(IssuerBurnUtxoSubcommand),
+    /// Seize a user's revealed funds and frozen UTXOs and reissue the amount into a recovery account
+    SeizeAndReissue(IssuerSeizeAndReissueSubcommand),
 }
 
 impl IssuerSubcommand {
     pub async fn run(self, mut context: CliContext) -> anyhow::Result<()> {
-        match self {
-            Self::Create(cmd) => {
-                let params = context.get_build_params().await?;
-                let transaction = transactions::build(params, cmd.into());
-                let result = context.submit_and_wait_transaction(transaction).await?;
-                print_result(&result)
+        let profile = context.profile();
+        let created_issuer = match &self {
+            Self::Create(cmd) => Some((cmd.token_symbol.to_string(), cmd.no_user_badge)),
+            _ => None,
+        };
+        let transaction = match self {
+            Self::Create(cmd) => cmd.into_transaction(&context)?,
+            Self::BatchBlacklist(cmd) => StableCoinTransaction::BatchBlacklistUsers {
+                user_ids: batch_file::read_user_ids(&cmd.file)?,
+                recall_revealed_balance: cmd.recall_revealed_balance,
+                issuer: cmd.issuer.resolve(&profile)?,
+            },
+            Self::BatchRecall(cmd) => StableCoinTransaction::BatchRecallRevealedTokens {
+                recalls: batch_file::read_user_amounts(&cmd.file)?,
+                issuer: cmd.issuer.resolve(&profile)?,
+            },
+            Self::BatchSetExchangeLimit(cmd) => StableCoinTransaction::BatchSetUserExchangeLimit {
+                limits: batch_file::read_user_amounts(&cmd.file)?,
+                issuer: cmd.issuer.resolve(&profile)?,
+            },
+            Self::IncreaseSupply(cmd) => StableCoinTransaction::IncreaseSupply {
+                issuer: cmd.issuer.resolve(&profile)?,
+                amount: cmd.amount,
+            },
+            Self::DecreaseSupply(cmd) => StableCoinTransaction::DecreaseSupply {
+                issuer: cmd.issuer.resolve(&profile)?,
+                amount: cmd.amount,
+            },
+            Self::Withdraw(cmd) => StableCoinTransaction::Withdraw {
+                issuer: cmd.issuer.resolve(&profile)?,
+                amount: cmd.amount,
+                destination: cmd.destination,
+            },
+            Self::Deposit(cmd) => StableCoinTransaction::Deposit {
+                issuer: cmd.issuer.resolve(&profile)?,
+                token_resource: config::arg_or_profile(
+                    cmd.token_resource,
+                    profile.token_resource,
+                    "token-resource",
+                    "token-resource",
+                )?,
+                amount: cmd.amount,
+            },
+            Self::CreateUser(cmd) => StableCoinTransaction::CreateUser {
+                issuer: cmd.issuer.resolve(&profile)?,
+                user_id: cmd.user_id,
+                user_account: cmd.user_account,
+            },
+            Self::CreateAdmin(cmd) => StableCoinTransaction::CreateAdmin {
+                issuer: cmd.issuer.resolve(&profile)?,
+                employee_id: cmd.employee_id,
+                destination: cmd.destination,
+            },
+            Self::Blacklist(cmd) => StableCoinTransaction::BlacklistUser {
+                issuer: cmd.issuer.resolve(&profile)?,
+                user_id: cmd.user_id,
+                recall_revealed_balance: cmd.recall_revealed_balance,
+                utxos_to_freeze: cmd.freeze_utxos,
+            },
+            Self::Unblacklist(cmd) => StableCoinTransaction::RemoveFromBlacklist {
+                issuer: cmd.issuer.resolve(&profile)?,
+                user_id: cmd.user_id,
+                user_account: cmd.user_account,
+            },
+            Self::SetLimit(cmd) => StableCoinTransaction::SetUserExchangeLimit {
+                issuer: cmd.issuer.resolve(&profile)?,
+                user_id: cmd.user_id,
+                limit: cmd.amount,
+            },
+            Self::SetFee(cmd) => cmd.into_transaction(&profile)?,
+            Self::Pause(issuer) => StableCoinTransaction::Pause {
+                issuer: issuer.resolve(&profile)?,
+            },
+            Self::Unpause(issuer) => StableCoinTransaction::Unpause {
+                issuer: issuer.resolve(&profile)?,
+            },
+            Self::FreezeUtxos(cmd) => StableCoinTransaction::FreezeUtxos {
+                issuer: cmd.issuer.resolve(&profile)?,
+                utxos: cmd.utxos,
+            },
+            Self::UnfreezeUtxos(cmd) => StableCoinTransaction::UnfreezeUtxos {
+                issuer: cmd.issuer.resolve(&profile)?,
+                utxos: cmd.utxos,
+            },
+            Self::Recall(cmd) => StableCoinTransaction::RecallRevealedTokens {
+                issuer: cmd.issuer.resolve(&profile)?,
+                user_id: cmd.user_id,
+                amount: cmd.amount,
+            },
+            Self::BurnUtxo(cmd) => {
+                let value_proof = fs::read_to_string(&cmd.value_proof_file).with_context(|| {
+                    format!("Failed to read {}", cmd.value_proof_file.display())
+                })?;
+                StableCoinTransaction::BurnUtxo {
+                    issuer: cmd.issuer.resolve(&profile)?,
+                    utxo: cmd.utxo,
+                    value_proof: serde_json::from_str(&value_proof)
+                        .context("Invalid value proof")?,
+                }
             }
+            Self::SeizeAndReissue(cmd) => StableCoinTransaction::SeizeAndReissue {
+                issuer: cmd.issuer.resolve(&profile)?,
+                user_id: cmd.user_id,
+                utxos: match &cmd.value_proofs_file {
+                    Some(path) => read_utxo_value_proofs(path)?,
+                    None => Vec::new(),
+                },
+                case_reference: cmd.case_reference,
+                recovery_account: cmd.recovery_account,
+            },
+        };
+
+        let params = context.get_build_params().await?;
+        let issuer_template = params.issuer_template;
+        let fee_account = params.fee_account;
+        let transaction = transactions::build(params, transaction);
+        if let Some(path) = context.export_path() {
+            transaction_file::write_unsigned_transaction(path, &transaction)?;
+            println!("Unsigned transaction written to {}", path.display());
+            return Ok(());
+        }
+        if context.is_dry_run() {
+            let result = context.dry_run_transaction(transaction).await?;
+            print_dry_run_result(&result);
+            return Ok(());
+        }
+        let result = context.submit_and_wait_transaction(transaction).await?;
+        print_result(&result);
+
+        if let Some((token_symbol, no_user_badge)) = created_issuer {
+            let Some(TransactionResult::Accept(diff)) = result.result.as_ref().map(|r| &r.result)
+            else {
+                return Ok(());
+            };
+            let mut profile = profile;
+            profile.wallet_url = Some(context.wallet_url());
+            profile.issuer_template = Some(issuer_template);
+            profile.fee_account = Some(fee_account);
+            profile.no_user_badge = no_user_badge;
+            profile.update_from_created_issuer(diff, issuer_template, &token_symbol);
+            context.save_profile(profile)?;
+            println!("Saved the new issuer to profile {}", context.profile_name());
         }
 
         Ok(())
@@ -69,25 +326,304 @@ impl IssuerSubcommand {
 }
 
 #[derive(Clone, Debug, clap::Args)]
+pub struct IssuerArgs {
+    /// The issuer component address. Defaults to the profile's issuer-component.
+    #[clap(long = "issuer", short = 'i', env = "ISSUER_COMPONENT")]
+    pub component: Option<ComponentAddress>,
+    /// The admin badge resource. The fee account must hold an admin badge with the required role. Defaults to the
+    /// profile's admin-badge-resource.
+    #[clap(long, short = 'b', env = "ADMIN_BADGE_RESOURCE")]
+    pub admin_badge_resource: Option<ResourceAddress>,
+    /// The issuer was instantiated from the issuer-no-user-badge template. Defaults to the profile's no-user-badge.
+    #[clap(long)]
+    pub no_user_badge: bool,
+}
+
+impl IssuerArgs {
+    pub fn resolve(self, profile: &Profile) -> anyhow::Result<IssuerAccess> {
+        Ok(IssuerAccess {
+            component: config::arg_or_profile(
+                self.component,
+                profile.issuer_component,
+                "issuer",
+                "issuer-component",
+            )?,
+            admin_badge_resource: config::arg_or_profile(
+                self.admin_badge_resource,
+                profile.admin_badge_resource,
+                "admin-badge-resource",
+                "admin-badge-resource",
+            )?,
+            no_user_badge: self.no_user_badge || profile.no_user_badge,
+        })
+    }
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerBatchBlacklistSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    /// A CSV file with one user ID per line, or a JSON array of user IDs
+    pub file: PathBuf,
+    /// Also recall the revealed stable coin balance of each blacklisted user
+    #[clap(long, short)]
+    pub recall_revealed_balance: bool,
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerBatchUserAmountsSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    /// A CSV file of user_id,amount lines, or a JSON array of {"user_id", "amount"} objects
+    pub file: PathBuf,
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerAmountSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    #[clap(value_parser = value_parsers::amount)]
+    pub amount: Amount,
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerWithdrawSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    #[clap(value_parser = value_parsers::amount)]
+    pub amount: Amount,
+    /// The account to send the tokens to. Defaults to the fee account.
+    #[clap(long, short)]
+    pub destination: Option<ComponentAddress>,
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerDepositSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    #[clap(value_parser = value_parsers::amount)]
+    pub amount: Amount,
+    /// The stable coin resource. Defaults to the profile's token-resource.
+    #[clap(long, short = 'r', env = "TOKEN_RESOURCE")]
+    pub token_resource: Option<ResourceAddress>,
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerUserAccountSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    pub user_id: UserId,
+    /// The account that holds the user's badge
+    pub user_account: ComponentAddress,
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerCreateAdminSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    pub employee_id: String,
+    /// The account to send the admin badge to. Defaults to the fee account.
+    #[clap(long, short)]
+    pub destination: Option<ComponentAddress>,
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerBlacklistSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    pub user_id: UserId,
+    /// Also recall the revealed stable coin balance of the user
+    #[clap(long, short)]
+    pub recall_revealed_balance: bool,
+    /// A UTXO known to belong to the user to freeze. May be given more than once.
+    #[clap(long = "freeze-utxo", value_parser = value_parsers::utxo_id)]
+    pub freeze_utxos: Vec<UtxoId>,
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerUserAmountSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    pub user_id: UserId,
+    #[clap(value_parser = value_parsers::amount)]
+    pub amount: Amount,
+}
+
+#[derive(Clone, Debug, clap::Args)]
+#[clap(group(clap::ArgGroup::new("fee").required(true)))]
+pub struct IssuerSetFeeSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    pub target: FeeTarget,
+    /// A fixed fee amount
+    #[clap(long, group = "fee", value_parser = value_parsers::amount)]
+    pub fixed: Option<Amount>,
+    /// A fee as a percentage (0-100) of the amount
+    #[clap(long, group = "fee", value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub percentage: Option<u8>,
+    /// A fee in basis points (0-10000) of the amount, where 1 bps is 0.01%
+    #[clap(long, group = "fee", value_parser = clap::value_parser!(u16).range(0..=10_000))]
+    pub bps: Option<u16>,
+    /// How a basis point fee is rounded
+    #[clap(long, value_enum, requires = "bps", default_value = "up")]
+    pub rounding: RoundingMode,
+    /// The minimum basis point fee
+    #[clap(long = "min-bps-fee", requires = "bps", value_parser = value_parsers::amount)]
+    pub min_fee: Option<Amount>,
+    /// The maximum basis point fee
+    #[clap(long = "max-bps-fee", requires = "bps", value_parser = value_parsers::amount)]
+    pub max_fee: Option<Amount>,
+    /// A JSON file with the fee tiers, e.g. [{"up_to": 1000, "fee": {"Fixed": 1}}, {"up_to": null, "fee":
+    /// {"Percentage": 1}}]. Each tier applies to amounts below its up_to, and the last tier has none.
+    #[clap(long, group = "fee", value_name = "FILE")]
+    pub tiers: Option<PathBuf>,
+}
+
+impl IssuerSetFeeSubcommand {
+    fn into_transaction(self, profile: &Profile) -> anyhow::Result<StableCoinTransaction> {
+        let fee = match (self.fixed, self.percentage, self.bps, self.tiers) {
+            (Some(fee), ..) => FeeSpec::Fixed(fee),
+            (None, Some(fee_perc), ..) => FeeSpec::Percentage(fee_perc),
+            (None, None, Some(bps), _) => FeeSpec::BasisPoints(BasisPointFee {
+                bps,
+                rounding: self.rounding,
+                min_fee: self.min_fee,
+                max_fee: self.max_fee,
+            }),
+            (None, None, None, Some(path)) => FeeSpec::Tiered(read_fee_tiers(&path)?),
+            (None, None, None, None) => {
+                unreachable!("clap requires one of --fixed, --percentage, --bps or --tiers")
+            }
+        };
+        Ok(StableCoinTransaction::SetFee {
+            issuer: self.issuer.resolve(profile)?,
+            target: self.target,
+            fee,
+        })
+    }
+}
+
+fn read_fee_tiers(path: &Path) -> anyhow::Result<Vec<FeeTier>> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid fee tiers in {}", path.display()))
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerUtxosSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    #[clap(required = true, value_parser = value_parsers::utxo_id)]
+    pub utxos: Vec<UtxoId>,
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerBurnUtxoSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    #[clap(value_parser = value_parsers::utxo_id)]
+    pub utxo: UtxoId,
+    /// A JSON file containing the stealth value proof of the UTXO
+    pub value_proof_file: PathBuf,
+}
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct IssuerSeizeAndReissueSubcommand {
+    #[clap(flatten)]
+    pub issuer: IssuerArgs,
+    pub user_id: UserId,
+    /// The reference of the legal case, recorded in every event
+    pub case_reference: String,
+    /// The account that receives the reissued tokens. For the issuer template it must hold a user badge.
+    pub recovery_account: ComponentAddress,
+    /// A JSON file mapping the hex IDs of the user's frozen UTXOs to their stealth value proofs. The UTXOs are burned
+    /// and their value is reissued along with the revealed funds.
+    #[clap(long = "value-proofs")]
+    pub value_proofs_file: Option<PathBuf>,
+}
+
+fn read_utxo_value_proofs(path: &Path) -> anyhow::Result<Vec<(UtxoId, StealthValueProof)>> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let proofs: BTreeMap<String, StealthValueProof> = serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid value proofs in {}", path.display()))?;
+    proofs
+        .into_iter()
+        .map(|(utxo, proof)| Ok((value_parsers::utxo_id(&utxo)?, proof)))
+        .collect()
+}
+
+#[derive(Clone, Debug, clap::Args)]
+#[clap(group(clap::ArgGroup::new("view_key_source").required(true)))]
 pub struct IssuerCreateSubcommand {
     #[clap(value_parser = value_parsers::amount)]
     pub initial_token_supply: Amount,
-    pub token_symbol: String,
+    #[clap(value_parser = value_parsers::amount)]
+    pub max_supply: Amount,
+    /// The token symbol, at most 8 bytes
+    #[clap(value_parser = value_parsers::token_symbol)]
+    pub token_symbol: MaxString<8>,
     #[clap(value_parser = value_parsers::metadata)]
     pub token_metadata: Metadata,
+    /// The number of decimal places of the token
+    #[clap(long, short, default_value = "8")]
+    pub divisibility: u8,
+    /// The hex encoded public view key of the token
+    #[clap(long, group = "view_key_source", value_parser = value_parsers::public_key)]
+    pub view_key: Option<RistrettoPublicKeyBytes>,
+    /// A file containing the hex encoded public view key of the token
+    #[clap(long, group = "view_key_source")]
+    pub view_key_file: Option<PathBuf>,
+    /// Generate a new view keypair and write its secret key to this file. Not allowed with --dry-run or --export,
+    /// since the secret key would be written for a transaction that is not submitted.
+    #[clap(long, group = "view_key_source", value_name = "SECRET_KEY_FILE")]
+    pub generate_view_key: Option<PathBuf>,
     #[clap(long, short, action=ArgAction::SetFalse)]
     pub enable_wrapped_token: bool,
+    /// The issuer template is issuer-no-user-badge, whose instantiate takes a component address allocation
+    #[clap(long)]
+    pub no_user_badge: bool,
 }
 
-impl From<IssuerCreateSubcommand> for StableCoinTransaction {
-    fn from(cmd: IssuerCreateSubcommand) -> Self {
-        StableCoinTransaction::CreateIssuer {
-            initial_token_supply: cmd.initial_token_supply,
-            token_symbol: cmd.token_symbol,
-            token_metadata: cmd.token_metadata,
-            // TODO: allow to specify via CLI
-            view_key: RistrettoPublicKeyBytes::default(),
-            enable_wrapped_token: cmd.enable_wrapped_token,
+impl IssuerCreateSubcommand {
+    fn into_transaction(self, context: &CliContext) -> anyhow::Result<StableCoinTransaction> {
+        if self.generate_view_key.is_some()
+            && (context.is_dry_run() || context.export_path().is_some())
+        {
+            anyhow::bail!(
+                "--generate-view-key cannot be used with --dry-run or --export, pass the public key with --view-key or \
+                 --view-key-file instead"
+            );
         }
+        let view_key = match (self.view_key, self.view_key_file, self.generate_view_key) {
+            (Some(view_key), _, _) => view_key,
+            (None, Some(path), _) => view_key::read_public_key(&path)?,
+            (None, None, Some(secret_path)) => {
+                let view_key = view_key::generate(&secret_path)?;
+                println!(
+                    "Generated view key {}. The secret key was written to {}",
+                    hex::encode(view_key.as_ref()),
+                    secret_path.display()
+                );
+                view_key
+            }
+            (None, None, None) => {
+                unreachable!(
+                    "clap requires one of --view-key, --view-key-file or --generate-view-key"
+                )
+            }
+        };
+
+        Ok(StableCoinTransaction::CreateIssuer {
+            initial_token_supply: self.initial_token_supply,
+            max_supply: self.max_supply,
+            token_symbol: self.token_symbol,
+            token_metadata: self.token_metadata,
+            divisibility: self.divisibility,
+            view_key,
+            enable_wrapped_token: self.enable_wrapped_token,
+            no_user_badge: self.no_user_badge,
+        })
     }
 }
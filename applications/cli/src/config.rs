@@ -0,0 +1,128 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Named profiles stored in a TOML config file, so that the addresses of a deployed issuer do not need to be passed
+//! to every command.
+//!
+//! ```toml
+//! active-profile = "local"
+//!
+//! [profiles.local]
+//! wallet-url = "http://localhost:9000/"
+//! issuer-template = "<template address>"
+//! issuer-component = "component_..."
+//! admin-badge-resource = "resource_..."
+//! token-resource = "resource_..."
+//! fee-account = "component_..."
+//! ```
+//!
+//! Values given on the command line or through environment variables take precedence over the profile.
+
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use tari_engine_types::substate::{SubstateDiff, SubstateValue};
+use tari_template_lib::models::{ComponentAddress, ResourceAddress, TemplateAddress};
+use url::Url;
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CliConfig {
+    /// The profile used when `--profile` is not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl CliConfig {
+    /// Loads the config file, or returns an empty config if it does not exist yet
+    pub fn load_or_default(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = toml::to_string_pretty(self)?;
+        fs::write(path, contents)
+            .with_context(|| format!("Failed to write config file {}", path.display()))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Profile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet_url: Option<Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer_template: Option<TemplateAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer_component: Option<ComponentAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_badge_resource: Option<ResourceAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_resource: Option<ResourceAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_account: Option<ComponentAddress>,
+    /// Whether the issuer was instantiated from the issuer-no-user-badge template
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_user_badge: bool,
+}
+
+impl Profile {
+    /// Records the issuer component, admin badge resource and token resource created by `instantiate`. The admin badge
+    /// is recognised by its `admin_badge` metadata and the token by its symbol, which tells it apart from the wrapped
+    /// token.
+    pub fn update_from_created_issuer(
+        &mut self,
+        diff: &SubstateDiff,
+        issuer_template: TemplateAddress,
+        token_symbol: &str,
+    ) {
+        for (id, substate) in diff.up_iter() {
+            match substate.substate_value() {
+                SubstateValue::Component(component)
+                    if component.template_address == issuer_template =>
+                {
+                    self.issuer_component = id.as_component_address();
+                }
+                SubstateValue::Resource(resource)
+                    if resource.metadata().get("admin_badge") == Some("true") =>
+                {
+                    self.admin_badge_resource = id.as_resource_address();
+                }
+                SubstateValue::Resource(resource)
+                    if resource.token_symbol() == Some(token_symbol) =>
+                {
+                    self.token_resource = id.as_resource_address();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Returns the value given on the command line, falling back to the profile
+pub fn arg_or_profile<T>(
+    arg: Option<T>,
+    profile_value: Option<T>,
+    arg_name: &str,
+    profile_key: &str,
+) -> anyhow::Result<T> {
+    arg.or(profile_value).ok_or_else(|| {
+        anyhow!(
+            "Missing {}. Pass --{} or set {} in the active profile",
+            profile_key,
+            arg_name,
+            profile_key
+        )
+    })
+}
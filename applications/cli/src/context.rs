@@ -2,42 +2,98 @@
 // SPDX-License-Identifier: BSD-3-Clause
 
 use crate::cli::CommonCli;
+use crate::config::{self, CliConfig, Profile, DEFAULT_PROFILE};
 use crate::transactions::BuildParams;
-use tari_template_lib::models::{Amount, TemplateAddress};
-use tari_transaction::UnsignedTransaction;
+use std::path::Path;
+use tari_template_lib::models::{Amount, ComponentAddress, TemplateAddress};
+use tari_transaction::{Transaction, TransactionId, UnsignedTransaction};
 use tari_wallet_daemon_client::types::{
-    AuthLoginAcceptRequest, AuthLoginRequest, TransactionSubmitRequest,
-    TransactionWaitResultRequest, TransactionWaitResultResponse,
+    AuthLoginAcceptRequest, AuthLoginRequest, TransactionSubmitDryRunRequest,
+    TransactionSubmitDryRunResponse, TransactionSubmitRequest, TransactionWaitResultRequest,
+    TransactionWaitResultResponse,
 };
 use tari_wallet_daemon_client::WalletDaemonClient;
+use url::Url;
+
+const DEFAULT_WALLET_URL: &str = "http://localhost:9000";
 
 pub struct CliContext {
     client: Option<WalletDaemonClient>,
     common_cli: CommonCli,
+    config: CliConfig,
 }
 
 impl CliContext {
-    pub fn new(common_cli: CommonCli) -> Self {
+    pub fn new(common_cli: CommonCli, config: CliConfig) -> Self {
         Self {
             client: None,
             common_cli,
+            config,
         }
     }
 
-    pub fn issuer_template(&self) -> TemplateAddress {
-        self.common_cli.issuer_template
+    pub fn profile_name(&self) -> &str {
+        self.common_cli
+            .profile
+            .as_deref()
+            .or(self.config.active_profile.as_deref())
+            .unwrap_or(DEFAULT_PROFILE)
+    }
+
+    pub fn profile(&self) -> Profile {
+        self.config
+            .profiles
+            .get(self.profile_name())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replaces the active profile and writes the config file
+    pub fn save_profile(&mut self, profile: Profile) -> anyhow::Result<()> {
+        let name = self.profile_name().to_string();
+        self.config.profiles.insert(name, profile);
+        self.config.save(&self.common_cli.config)
+    }
+
+    pub fn wallet_url(&self) -> Url {
+        self.common_cli
+            .wallet_url
+            .clone()
+            .or(self.profile().wallet_url)
+            .unwrap_or_else(|| DEFAULT_WALLET_URL.parse().unwrap())
+    }
+
+    pub fn issuer_template(&self) -> anyhow::Result<TemplateAddress> {
+        config::arg_or_profile(
+            self.common_cli.issuer_template,
+            self.profile().issuer_template,
+            "issuer-template",
+            "issuer-template",
+        )
+    }
+
+    fn fee_account(&self) -> Option<ComponentAddress> {
+        self.common_cli.fee_account.or(self.profile().fee_account)
     }
 
     pub fn max_fee(&self) -> Amount {
         self.common_cli.max_fee
     }
 
+    pub fn is_dry_run(&self) -> bool {
+        self.common_cli.dry_run
+    }
+
+    pub fn export_path(&self) -> Option<&Path> {
+        self.common_cli.export.as_deref()
+    }
+
     pub async fn connect_wallet_client(&mut self) -> anyhow::Result<&mut WalletDaemonClient> {
         if let Some(ref mut client_mut) = self.client {
             return Ok(client_mut);
         }
 
-        let mut client = WalletDaemonClient::connect(self.common_cli.wallet_url.clone(), None)?;
+        let mut client = WalletDaemonClient::connect(self.wallet_url(), None)?;
 
         // "Log in" as admin (TODO: exchange secret credentials for auth token)
         let resp = client
@@ -61,7 +117,7 @@ impl CliContext {
     }
 
     pub(crate) async fn get_build_params(&mut self) -> anyhow::Result<BuildParams> {
-        let fee_account = match self.common_cli.fee_account {
+        let fee_account = match self.fee_account() {
             Some(fee_account) => fee_account,
             None => {
                 let client_mut = self.connect_wallet_client().await?;
@@ -72,16 +128,14 @@ impl CliContext {
 
         Ok(BuildParams {
             fee_account,
-            issuer_template: self.issuer_template(),
+            issuer_template: self.issuer_template()?,
             max_fee: self.max_fee(),
         })
     }
 
-    pub(crate) async fn submit_and_wait_transaction(
-        &mut self,
-        transaction: UnsignedTransaction,
-    ) -> anyhow::Result<TransactionWaitResultResponse> {
-        let fee_account = self.common_cli.fee_account;
+    /// Returns the index of the key that signs for the fee account
+    async fn fee_account_key_index(&mut self) -> anyhow::Result<u64> {
+        let fee_account = self.fee_account();
         let client_mut = self.connect_wallet_client().await?;
 
         let resp = match fee_account {
@@ -89,16 +143,69 @@ impl CliContext {
             None => client_mut.accounts_get_default().await?,
         };
 
+        Ok(resp.account.key_index)
+    }
+
+    /// Has the wallet daemon execute the transaction against the current state. The transaction is not submitted to
+    /// the network.
+    pub(crate) async fn dry_run_transaction(
+        &mut self,
+        transaction: UnsignedTransaction,
+    ) -> anyhow::Result<TransactionSubmitDryRunResponse> {
+        let signing_key_index = self.fee_account_key_index().await?;
+        let client_mut = self.connect_wallet_client().await?;
+        let resp = client_mut
+            .submit_transaction_dry_run(TransactionSubmitDryRunRequest {
+                transaction: Some(transaction),
+                signing_key_index: Some(signing_key_index),
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub(crate) async fn submit_and_wait_transaction(
+        &mut self,
+        transaction: UnsignedTransaction,
+    ) -> anyhow::Result<TransactionWaitResultResponse> {
+        let signing_key_index = self.fee_account_key_index().await?;
+        let client_mut = self.connect_wallet_client().await?;
         let resp = client_mut
             .submit_transaction(TransactionSubmitRequest {
                 transaction: Some(transaction),
-                signing_key_index: Some(resp.account.key_index),
+                signing_key_index: Some(signing_key_index),
                 ..Default::default()
             })
             .await?;
+
+        self.wait_transaction_result(resp.transaction_id).await
+    }
+
+    /// Submits a transaction that has already been signed, e.g. one exported with `--export` and signed offline
+    pub(crate) async fn submit_and_wait_signed_transaction(
+        &mut self,
+        transaction: Transaction,
+    ) -> anyhow::Result<TransactionWaitResultResponse> {
+        let client_mut = self.connect_wallet_client().await?;
+        let resp = client_mut
+            .submit_transaction(TransactionSubmitRequest {
+                signed_transaction: Some(transaction),
+                ..Default::default()
+            })
+            .await?;
+
+        self.wait_transaction_result(resp.transaction_id).await
+    }
+
+    async fn wait_transaction_result(
+        &mut self,
+        transaction_id: TransactionId,
+    ) -> anyhow::Result<TransactionWaitResultResponse> {
+        let client_mut = self.connect_wallet_client().await?;
         let resp = client_mut
             .wait_transaction_result(TransactionWaitResultRequest {
-                transaction_id: resp.transaction_id,
+                transaction_id,
                 timeout_secs: None,
             })
             .await?;
@@ -1,19 +1,27 @@
 use crate::cli::{Cli, Command};
+use crate::config::CliConfig;
 use crate::context::CliContext;
 
+mod batch_file;
 mod cli;
+mod config;
 mod context;
 mod print_result;
+mod transaction_file;
 mod transactions;
 mod value_parsers;
+mod view_key;
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::init();
 
-    let context = CliContext::new(cli.common);
+    let config = CliConfig::load_or_default(&cli.common.config)?;
+    let context = CliContext::new(cli.common, config);
     match cli.command {
         Command::Issuer(cmd) => cmd.run(context).await?,
+        Command::Sign(cmd) => cmd.run(&context)?,
+        Command::Submit(cmd) => cmd.run(context).await?,
     }
 
     Ok(())
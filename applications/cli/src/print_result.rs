@@ -8,7 +8,9 @@ use tari_engine_types::instruction_result::InstructionResult;
 use tari_engine_types::substate::{SubstateDiff, SubstateId, SubstateValue};
 use tari_template_abi::Type;
 use tari_template_lib::models::{Amount, BucketId, NonFungibleId};
-use tari_wallet_daemon_client::types::TransactionWaitResultResponse;
+use tari_wallet_daemon_client::types::{
+    TransactionSubmitDryRunResponse, TransactionWaitResultResponse,
+};
 
 pub fn print_result(resp: &TransactionWaitResultResponse) {
     println!("Transaction ID: {}", resp.transaction_id);
@@ -28,6 +30,22 @@ pub fn print_result(resp: &TransactionWaitResultResponse) {
     }
 }
 
+pub fn print_dry_run_result(resp: &TransactionSubmitDryRunResponse) {
+    println!("DRY RUN: the transaction was not submitted");
+    println!();
+
+    let finalize = &resp.result.finalize;
+    summarize_finalize_result(finalize);
+
+    println!();
+    println!(
+        "Estimated fee: {}",
+        finalize.fee_receipt.total_fees_charged()
+    );
+    println!();
+    println!("OVERALL DECISION: {}", finalize.result);
+}
+
 pub fn print_substate_diff(diff: &SubstateDiff) {
     for (address, substate) in diff.up_iter() {
         println!("️🌲 UP substate {} (v{})", address, substate.version(),);
@@ -0,0 +1,126 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Reads and writes transactions for signing on another machine.
+//!
+//! Files ending in `.cbor` are encoded as CBOR, the same encoding the engine uses. Any other file is JSON.
+
+use anyhow::{anyhow, Context};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tari_crypto::ristretto::RistrettoSecretKey;
+use tari_crypto::tari_utilities::byte_array::ByteArray;
+use tari_transaction::{Transaction, UnsignedTransaction};
+
+pub fn write_unsigned_transaction(
+    path: &Path,
+    transaction: &UnsignedTransaction,
+) -> anyhow::Result<()> {
+    write_transaction(path, transaction)
+}
+
+pub fn read_unsigned_transaction(path: &Path) -> anyhow::Result<UnsignedTransaction> {
+    read_transaction(path)
+}
+
+pub fn write_signed_transaction(path: &Path, transaction: &Transaction) -> anyhow::Result<()> {
+    write_transaction(path, transaction)
+}
+
+pub fn read_signed_transaction(path: &Path) -> anyhow::Result<Transaction> {
+    read_transaction(path)
+}
+
+/// Reads the hex encoded secret key that seals a transaction. It must belong to the owner of the fee account.
+pub fn read_signing_key(path: &Path) -> anyhow::Result<RistrettoSecretKey> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read signing key file {}", path.display()))?;
+    let bytes = hex::decode(contents.trim())
+        .with_context(|| format!("Invalid signing key in {}", path.display()))?;
+    RistrettoSecretKey::from_canonical_bytes(&bytes)
+        .map_err(|e| anyhow!("Invalid signing key in {}: {e}", path.display()))
+}
+
+fn write_transaction<T: Serialize>(path: &Path, transaction: &T) -> anyhow::Result<()> {
+    let contents = if is_cbor(path) {
+        tari_bor::encode(transaction).map_err(|e| anyhow!("Failed to encode transaction: {e}"))?
+    } else {
+        serde_json::to_vec_pretty(transaction)?
+    };
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn read_transaction<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let contents = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if is_cbor(path) {
+        tari_bor::decode(&contents)
+            .map_err(|e| anyhow!("Invalid transaction in {}: {e}", path.display()))
+    } else {
+        serde_json::from_slice(&contents)
+            .with_context(|| format!("Invalid transaction in {}", path.display()))
+    }
+}
+
+fn is_cbor(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("cbor"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+    use std::path::PathBuf;
+    use tari_crypto::keys::PublicKey;
+    use tari_crypto::ristretto::RistrettoPublicKey;
+    use tari_template_lib::args;
+    use tari_template_lib::models::{Amount, ComponentAddress};
+
+    fn test_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("stable_coin_cli_{}_{name}", std::process::id()))
+    }
+
+    fn unsigned_transaction() -> UnsignedTransaction {
+        let account = ComponentAddress::from_array([1; 32]);
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account, Amount::new(2000))
+            .call_method(account, "withdraw", args!(Amount::new(100)))
+            .build_unsigned_transaction()
+    }
+
+    fn assert_round_trips(name: &str) {
+        let (secret_key, _) = RistrettoPublicKey::random_keypair(&mut OsRng);
+        let unsigned = unsigned_transaction();
+        let signed = unsigned.clone().finish().seal(&secret_key);
+
+        let unsigned_path = test_file(&format!("unsigned.{name}"));
+        write_unsigned_transaction(&unsigned_path, &unsigned).unwrap();
+        let read_unsigned = read_unsigned_transaction(&unsigned_path).unwrap();
+        fs::remove_file(&unsigned_path).unwrap();
+        assert_eq!(
+            serde_json::to_value(&read_unsigned).unwrap(),
+            serde_json::to_value(&unsigned).unwrap()
+        );
+
+        let signed_path = test_file(&format!("signed.{name}"));
+        write_signed_transaction(&signed_path, &signed).unwrap();
+        let read_signed = read_signed_transaction(&signed_path).unwrap();
+        fs::remove_file(&signed_path).unwrap();
+        assert_eq!(
+            serde_json::to_value(&read_signed).unwrap(),
+            serde_json::to_value(&signed).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_round_trips_json_transactions() {
+        assert_round_trips("json");
+    }
+
+    #[test]
+    fn it_round_trips_cbor_transactions() {
+        assert_round_trips("cbor");
+    }
+}
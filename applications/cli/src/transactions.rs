@@ -1,20 +1,189 @@
 // Copyright 2024 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
+use crate::batch_file::UserId;
+use serde::{Deserialize, Serialize};
 use tari_template_lib::args;
-use tari_template_lib::crypto::RistrettoPublicKeyBytes;
+use tari_template_lib::args::Arg;
+use tari_template_lib::crypto::{RistrettoPublicKeyBytes, StealthValueProof};
 use tari_template_lib::models::{Amount, Metadata, TemplateAddress};
-use tari_template_lib::prelude::ComponentAddress;
-use tari_transaction::{Transaction, UnsignedTransaction};
+use tari_template_lib::prelude::{ComponentAddress, MaxString, ResourceAddress, UtxoId};
+use tari_transaction::{Transaction, TransactionBuilder, UnsignedTransaction};
 
 pub enum StableCoinTransaction {
     CreateIssuer {
         initial_token_supply: Amount,
-        token_symbol: String,
+        max_supply: Amount,
+        token_symbol: MaxString<8>,
         token_metadata: Metadata,
+        divisibility: u8,
         view_key: RistrettoPublicKeyBytes,
         enable_wrapped_token: bool,
+        /// Whether the issuer template is issuer-no-user-badge, whose `instantiate` takes a component address
+        /// allocation
+        no_user_badge: bool,
     },
+    BatchBlacklistUsers {
+        issuer: IssuerAccess,
+        user_ids: Vec<UserId>,
+        recall_revealed_balance: bool,
+    },
+    BatchRecallRevealedTokens {
+        issuer: IssuerAccess,
+        recalls: Vec<(UserId, Amount)>,
+    },
+    BatchSetUserExchangeLimit {
+        issuer: IssuerAccess,
+        limits: Vec<(UserId, Amount)>,
+    },
+    IncreaseSupply {
+        issuer: IssuerAccess,
+        amount: Amount,
+    },
+    DecreaseSupply {
+        issuer: IssuerAccess,
+        amount: Amount,
+    },
+    /// Withdraws tokens from the issuer vault into the destination account, or the fee account if None. The
+    /// issuer-no-user-badge template deposits into the destination itself.
+    Withdraw {
+        issuer: IssuerAccess,
+        amount: Amount,
+        destination: Option<ComponentAddress>,
+    },
+    /// Deposits tokens held by the fee account into the issuer vault
+    Deposit {
+        issuer: IssuerAccess,
+        token_resource: ResourceAddress,
+        amount: Amount,
+    },
+    /// Creates a user and deposits their badge into the user's account
+    CreateUser {
+        issuer: IssuerAccess,
+        user_id: UserId,
+        user_account: ComponentAddress,
+    },
+    /// Creates an admin and deposits their badge into the destination account, or the fee account if None
+    CreateAdmin {
+        issuer: IssuerAccess,
+        employee_id: String,
+        destination: Option<ComponentAddress>,
+    },
+    BlacklistUser {
+        issuer: IssuerAccess,
+        user_id: UserId,
+        recall_revealed_balance: bool,
+        utxos_to_freeze: Vec<UtxoId>,
+    },
+    /// Removes a user from the blacklist and returns their badge to the user's account
+    RemoveFromBlacklist {
+        issuer: IssuerAccess,
+        user_id: UserId,
+        user_account: ComponentAddress,
+    },
+    SetUserExchangeLimit {
+        issuer: IssuerAccess,
+        user_id: UserId,
+        limit: Amount,
+    },
+    SetFee {
+        issuer: IssuerAccess,
+        target: FeeTarget,
+        fee: FeeSpec,
+    },
+    Pause {
+        issuer: IssuerAccess,
+    },
+    Unpause {
+        issuer: IssuerAccess,
+    },
+    FreezeUtxos {
+        issuer: IssuerAccess,
+        utxos: Vec<UtxoId>,
+    },
+    UnfreezeUtxos {
+        issuer: IssuerAccess,
+        utxos: Vec<UtxoId>,
+    },
+    RecallRevealedTokens {
+        issuer: IssuerAccess,
+        user_id: UserId,
+        amount: Amount,
+    },
+    BurnUtxo {
+        issuer: IssuerAccess,
+        utxo: UtxoId,
+        value_proof: StealthValueProof,
+    },
+    /// Seizes the user's revealed funds and the given frozen UTXOs and deposits the reissued tokens into the recovery
+    /// account, which must hold a user badge
+    SeizeAndReissue {
+        issuer: IssuerAccess,
+        user_id: UserId,
+        utxos: Vec<(UtxoId, StealthValueProof)>,
+        case_reference: String,
+        recovery_account: ComponentAddress,
+    },
+}
+
+/// The fee charged by the issuer that a `SetFee` transaction configures
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum FeeTarget {
+    Transfer,
+    WrappedExchange,
+    /// The fee charged when exchanging wrapped tokens back for stable tokens
+    Redemption,
+}
+
+impl FeeTarget {
+    fn as_str(&self) -> &'static str {
+        match self {
+            FeeTarget::Transfer => "transfer",
+            FeeTarget::WrappedExchange => "wrapped_exchange",
+            FeeTarget::Redemption => "redemption",
+        }
+    }
+}
+
+/// Mirrors the issuer template's `FeeSpec`, which is encoded by variant and field name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FeeSpec {
+    Fixed(Amount),
+    Percentage(u8),
+    BasisPoints(BasisPointFee),
+    Tiered(Vec<FeeTier>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasisPointFee {
+    pub bps: u16,
+    pub rounding: RoundingMode,
+    pub min_fee: Option<Amount>,
+    pub max_fee: Option<Amount>,
+}
+
+/// A tier of a tiered fee, which applies to amounts below `up_to`. The last tier has no `up_to`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeTier {
+    pub up_to: Option<Amount>,
+    pub fee: FeeSpec,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, clap::ValueEnum)]
+pub enum RoundingMode {
+    Up,
+    Down,
+    /// Round to nearest, ties to the even value (banker's rounding)
+    HalfEven,
+}
+
+/// The issuer component to call and the admin badge that authorizes the call. The badge must be held by the fee
+/// account.
+pub struct IssuerAccess {
+    pub component: ComponentAddress,
+    pub admin_badge_resource: ResourceAddress,
+    /// Whether the issuer was instantiated from the issuer-no-user-badge template
+    pub no_user_badge: bool,
 }
 
 pub struct BuildParams {
@@ -27,42 +196,233 @@ pub fn build(params: BuildParams, st_transaction: StableCoinTransaction) -> Unsi
     match st_transaction {
         StableCoinTransaction::CreateIssuer {
             initial_token_supply,
+            max_supply,
             token_symbol,
             token_metadata,
+            divisibility,
             view_key,
             enable_wrapped_token,
+            no_user_badge,
         } => create_issuer(
             params,
             initial_token_supply,
+            max_supply,
             token_symbol,
             token_metadata,
+            divisibility,
             view_key,
             enable_wrapped_token,
+            no_user_badge,
+        ),
+        StableCoinTransaction::BatchBlacklistUsers {
+            issuer,
+            user_ids,
+            recall_revealed_balance,
+        } => call_admin_method(
+            params,
+            issuer,
+            "batch_blacklist_users",
+            args!(user_ids, recall_revealed_balance),
+        ),
+        StableCoinTransaction::BatchRecallRevealedTokens { issuer, recalls } => call_admin_method(
+            params,
+            issuer,
+            "batch_recall_revealed_tokens",
+            args!(recalls),
+        ),
+        StableCoinTransaction::BatchSetUserExchangeLimit { issuer, limits } => call_admin_method(
+            params,
+            issuer,
+            "batch_set_user_exchange_limit",
+            args!(limits),
+        ),
+        StableCoinTransaction::IncreaseSupply { issuer, amount } => call_admin_method(
+            params,
+            issuer,
+            "increase_supply",
+            args!(Workspace("proof"), amount),
+        ),
+        StableCoinTransaction::DecreaseSupply { issuer, amount } => {
+            call_admin_method(params, issuer, "decrease_supply", args!(amount))
+        }
+        StableCoinTransaction::Withdraw {
+            issuer,
+            amount,
+            destination,
+        } => {
+            let destination = destination.unwrap_or(params.fee_account);
+            if issuer.no_user_badge {
+                call_admin_method(params, issuer, "withdraw", args!(amount, destination))
+            } else {
+                call_admin_method_and_deposit(
+                    params,
+                    issuer,
+                    "withdraw",
+                    args!(amount),
+                    destination,
+                )
+            }
+        }
+        StableCoinTransaction::Deposit {
+            issuer,
+            token_resource,
+            amount,
+        } => deposit(params, issuer, token_resource, amount),
+        StableCoinTransaction::CreateUser {
+            issuer,
+            user_id,
+            user_account,
+        } => call_admin_method_and_deposit(
+            params,
+            issuer,
+            "create_new_user",
+            args!(user_id, user_account),
+            user_account,
+        ),
+        StableCoinTransaction::CreateAdmin {
+            issuer,
+            employee_id,
+            destination,
+        } => {
+            let destination = destination.unwrap_or(params.fee_account);
+            call_admin_method_and_deposit(
+                params,
+                issuer,
+                "create_new_admin",
+                args!(employee_id),
+                destination,
+            )
+        }
+        StableCoinTransaction::BlacklistUser {
+            issuer,
+            user_id,
+            recall_revealed_balance,
+            utxos_to_freeze,
+        } => call_admin_method(
+            params,
+            issuer,
+            "blacklist_user",
+            args!(user_id, recall_revealed_balance, utxos_to_freeze),
+        ),
+        StableCoinTransaction::RemoveFromBlacklist {
+            issuer,
+            user_id,
+            user_account,
+        } => call_admin_method_and_deposit(
+            params,
+            issuer,
+            "remove_from_blacklist",
+            args!(user_id),
+            user_account,
+        ),
+        StableCoinTransaction::SetUserExchangeLimit {
+            issuer,
+            user_id,
+            limit,
+        } => call_admin_method(
+            params,
+            issuer,
+            "set_user_exchange_limit",
+            args!(user_id, limit),
+        ),
+        StableCoinTransaction::SetFee {
+            issuer,
+            target,
+            fee,
+        } => {
+            let (method, args) = match (target, fee) {
+                // The redemption fee has a single setter that takes the whole fee spec
+                (FeeTarget::Redemption, fee) => {
+                    ("set_config_redemption_fee".to_string(), args!(fee))
+                }
+                (target, FeeSpec::Fixed(fee)) => (fee_setter(target, "fixed"), args!(fee)),
+                (target, FeeSpec::Percentage(fee_perc)) => {
+                    (fee_setter(target, "percentage"), args!(fee_perc))
+                }
+                (target, FeeSpec::BasisPoints(fee)) => (
+                    fee_setter(target, "basis_points"),
+                    args!(fee.bps, fee.rounding, fee.min_fee, fee.max_fee),
+                ),
+                (target, FeeSpec::Tiered(tiers)) => (fee_setter(target, "tiers"), args!(tiers)),
+            };
+            call_admin_method(params, issuer, &method, args)
+        }
+        StableCoinTransaction::Pause { issuer } => {
+            call_admin_method(params, issuer, "pause", args!(Workspace("proof")))
+        }
+        StableCoinTransaction::Unpause { issuer } => {
+            call_admin_method(params, issuer, "unpause", args!(Workspace("proof")))
+        }
+        StableCoinTransaction::FreezeUtxos { issuer, utxos } => {
+            call_admin_method(params, issuer, "freeze_utxos", args!(utxos))
+        }
+        StableCoinTransaction::UnfreezeUtxos { issuer, utxos } => {
+            call_admin_method(params, issuer, "unfreeze_utxos", args!(utxos))
+        }
+        StableCoinTransaction::RecallRevealedTokens {
+            issuer,
+            user_id,
+            amount,
+        } => call_admin_method(
+            params,
+            issuer,
+            "recall_revealed_tokens",
+            args!(user_id, amount),
+        ),
+        StableCoinTransaction::BurnUtxo {
+            issuer,
+            utxo,
+            value_proof,
+        } => call_admin_method(params, issuer, "burn_utxo", args!(utxo, value_proof)),
+        StableCoinTransaction::SeizeAndReissue {
+            issuer,
+            user_id,
+            utxos,
+            case_reference,
+            recovery_account,
+        } => call_admin_method_and_deposit(
+            params,
+            issuer,
+            "seize_and_reissue",
+            args!(user_id, utxos, case_reference),
+            recovery_account,
         ),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn create_issuer(
     params: BuildParams,
     initial_token_supply: Amount,
-    token_symbol: String,
+    max_supply: Amount,
+    token_symbol: MaxString<8>,
     token_metadata: Metadata,
+    divisibility: u8,
     view_key: RistrettoPublicKeyBytes,
     enable_wrapped_token: bool,
+    no_user_badge: bool,
 ) -> UnsignedTransaction {
-    Transaction::builder()
-        .fee_transaction_pay_from_component(params.fee_account, params.max_fee)
-        .call_function(
-            params.issuer_template,
-            "instantiate",
-            args!(
-                initial_token_supply,
-                token_symbol,
-                token_metadata,
-                view_key,
-                enable_wrapped_token
-            ),
-        )
+    let mut builder = Transaction::builder()
+        .fee_transaction_pay_from_component(params.fee_account, params.max_fee);
+    let mut instantiate_args = Vec::new();
+    // The issuer-no-user-badge template restricts its token to its own address, which it takes as an allocation
+    if no_user_badge {
+        builder = builder.allocate_component_address("issuer_address");
+        instantiate_args.extend(args!(Workspace("issuer_address")));
+    }
+    instantiate_args.extend(args!(
+        initial_token_supply,
+        max_supply,
+        token_symbol,
+        token_metadata,
+        divisibility,
+        view_key,
+        enable_wrapped_token,
+        // Use the template's default config
+        None::<()>
+    ));
+    builder
+        .call_function(params.issuer_template, "instantiate", instantiate_args)
         .put_last_instruction_output_on_workspace("issuer_badge")
         .call_method(
             params.fee_account,
@@ -71,3 +431,62 @@ fn create_issuer(
         )
         .build_unsigned_transaction()
 }
+
+/// Returns the name of the issuer method that sets the target's fee of the given kind
+fn fee_setter(target: FeeTarget, kind: &str) -> String {
+    format!("set_config_{}_fee_{}", target.as_str(), kind)
+}
+
+fn deposit(
+    params: BuildParams,
+    issuer: IssuerAccess,
+    token_resource: ResourceAddress,
+    amount: Amount,
+) -> UnsignedTransaction {
+    admin_transaction_builder(&params, &issuer)
+        .call_method(
+            params.fee_account,
+            "withdraw",
+            args!(token_resource, amount),
+        )
+        .put_last_instruction_output_on_workspace("tokens")
+        .call_method(issuer.component, "deposit", args!(Workspace("tokens")))
+        .drop_all_proofs_in_workspace()
+        .build_unsigned_transaction()
+}
+
+fn call_admin_method(
+    params: BuildParams,
+    issuer: IssuerAccess,
+    method: &str,
+    args: Vec<Arg>,
+) -> UnsignedTransaction {
+    admin_transaction_builder(&params, &issuer)
+        .call_method(issuer.component, method, args)
+        .drop_all_proofs_in_workspace()
+        .build_unsigned_transaction()
+}
+
+/// Calls an admin method that returns a bucket and deposits the bucket into the destination account
+fn call_admin_method_and_deposit(
+    params: BuildParams,
+    issuer: IssuerAccess,
+    method: &str,
+    args: Vec<Arg>,
+    destination: ComponentAddress,
+) -> UnsignedTransaction {
+    admin_transaction_builder(&params, &issuer)
+        .call_method(issuer.component, method, args)
+        .put_last_instruction_output_on_workspace("bucket")
+        .call_method(destination, "deposit", args!(Workspace("bucket")))
+        .drop_all_proofs_in_workspace()
+        .build_unsigned_transaction()
+}
+
+/// Pays the fee and puts a proof of the admin badge on the workspace as "proof"
+fn admin_transaction_builder(params: &BuildParams, issuer: &IssuerAccess) -> TransactionBuilder {
+    Transaction::builder()
+        .fee_transaction_pay_from_component(params.fee_account, params.max_fee)
+        .create_proof(params.fee_account, issuer.admin_badge_resource)
+        .put_last_instruction_output_on_workspace("proof")
+}
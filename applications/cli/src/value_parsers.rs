@@ -1,12 +1,29 @@
 // Copyright 2024 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
+use tari_template_lib::crypto::RistrettoPublicKeyBytes;
 use tari_template_lib::models::{Amount, Metadata};
+use tari_template_lib::prelude::{MaxString, UtxoId};
 
 pub fn amount(s: &str) -> anyhow::Result<Amount> {
     Ok(Amount::new(s.parse()?))
 }
 
+pub fn utxo_id(s: &str) -> anyhow::Result<UtxoId> {
+    UtxoId::from_hex(s).map_err(|e| anyhow::anyhow!("Invalid UTXO ID: {e}"))
+}
+
+pub fn public_key(s: &str) -> anyhow::Result<RistrettoPublicKeyBytes> {
+    let bytes = hex::decode(s)?;
+    RistrettoPublicKeyBytes::try_from(bytes.as_slice())
+        .map_err(|e| anyhow::anyhow!("Invalid public key: {e}"))
+}
+
+pub fn token_symbol(s: &str) -> anyhow::Result<MaxString<8>> {
+    MaxString::try_from(s)
+        .map_err(|_| anyhow::anyhow!("Token symbol must be at most 8 bytes, got {}", s.len()))
+}
+
 pub fn metadata(s: &str) -> anyhow::Result<Metadata> {
     s.split(',').fold(Ok(Metadata::new()), |metadata, pair| {
         let mut metadata = metadata?;
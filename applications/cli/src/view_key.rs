@@ -0,0 +1,44 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! The view key given to `instantiate`, which lets the holder of the secret key see the values of stealth UTXOs.
+
+use crate::value_parsers;
+use anyhow::{anyhow, Context};
+use rand::rngs::OsRng;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use tari_crypto::keys::PublicKey;
+use tari_crypto::ristretto::RistrettoPublicKey;
+use tari_crypto::tari_utilities::byte_array::ByteArray;
+use tari_template_lib::crypto::RistrettoPublicKeyBytes;
+
+/// Reads a hex encoded public key from a file
+pub fn read_public_key(path: &Path) -> anyhow::Result<RistrettoPublicKeyBytes> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read view key file {}", path.display()))?;
+    value_parsers::public_key(contents.trim())
+        .with_context(|| format!("Invalid view key in {}", path.display()))
+}
+
+/// Generates a new view keypair, writes the hex encoded secret key to `secret_path` and returns the public key. An
+/// existing file is never overwritten, since it may hold the only copy of another view key.
+pub fn generate(secret_path: &Path) -> anyhow::Result<RistrettoPublicKeyBytes> {
+    let (secret_key, public_key) = RistrettoPublicKey::random_keypair(&mut OsRng);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(secret_path)
+        .with_context(|| format!("Failed to create {}", secret_path.display()))?;
+    writeln!(file, "{}", hex::encode(secret_key.as_bytes()))?;
+
+    RistrettoPublicKeyBytes::try_from(public_key.as_bytes())
+        .map_err(|e| anyhow!("Invalid public key: {e}"))
+}
@@ -28,7 +28,7 @@ import Button from "@mui/material/Button";
 import {ComponentAddress, ResourceAddress} from "@tari-project/typescript-bindings";
 import {DataTableCell} from "../../components/StyledComponents";
 import {useNavigate} from "react-router-dom";
-import {getCborValueByPath, SimpleTransactionResult} from "@tari-project/tarijs-all";
+import {SimpleTransactionResult} from "@tari-project/tarijs-all";
 
 interface Props {
     issuerId: ComponentAddress;
@@ -79,19 +79,12 @@ function ManageUser(props: Props) {
 
     const handleOnRevoke = async () => {
         await runQuery(async () => {
-            const substate = await provider.getSubstate(userAccount) as any;
-            const vaults = getCborValueByPath(substate.value.Component.body.state, "$.vaults") as Record<string, string>;
-            const vaultToRevoke = vaults[userBadgeResource];
-            if (!vaultToRevoke) {
-                throw new Error(`User does not have a stable coin user badge ${userBadgeResource}`);
-            }
-
             const result = await provider.revokeUserAccess(
                 props.issuerId,
                 props.adminAuthBadge,
                 userBadgeResource,
                 props.userId,
-                vaultToRevoke,
+                userAccount,
             );
             if (result.accept) {
                 props.onChange?.(result);
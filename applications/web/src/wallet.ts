@@ -18,7 +18,6 @@ import {
     SubstateRequirement,
     SubstateType,
     UtxoAddress,
-    VaultId,
 } from "@tari-project/typescript-bindings";
 
 const NETWORK = parseInt(import.meta.env.VITE_TARI_NETWORK) as Network || Network.Igor;
@@ -159,7 +158,7 @@ export default class TariWallet {
         amount: number,
         fee: number = 2000,
     ): Promise<SimpleTransactionResult> {
-        return await this.callRestrictedMethod(component_address, badge_resource, "increase_supply", [amount], empty, [], fee);
+        return await this.callRestrictedMethod(component_address, badge_resource, "increase_supply", [{Workspace: {id: 0, offset: null}}, amount], empty, [], fee);
     }
 
     public decreaseSupply(
@@ -234,7 +233,7 @@ export default class TariWallet {
         adminBadgeResource: ResourceAddress,
         userBadgeResource: ResourceAddress,
         userId: number,
-        vaultId: VaultId,
+        userAccount: ComponentAddress,
         fee: number = 2000,
     ): Promise<SimpleTransactionResult> {
         const extraInputs = [
@@ -242,12 +241,16 @@ export default class TariWallet {
                 substate_id: `nft_${userBadgeResource}_u64_${userId}`,
                 version: null,
             },
+            {
+                substate_id: userAccount,
+                version: null,
+            },
         ] as SubstateRequirement[];
         return await this.callRestrictedMethod(
             issuerComponent,
             adminBadgeResource,
             "blacklist_user",
-            [vaultId, userId],
+            [userId, false, []],
             empty,
             extraInputs,
             fee,
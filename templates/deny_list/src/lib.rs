@@ -0,0 +1,247 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! A deny list of accounts and transaction signers shared by the stable coin issuer templates.
+//!
+//! The list is generic over the component address and public key types so that templates built against different
+//! `tari_template_lib` versions can all use it. Each entry may block an account, a public key or both, and can be
+//! found and removed by either.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+pub type DenyListEntryId = u64;
+
+/// Identifies why an entry was denied, e.g. the sanctions list it came from. The meaning of each code is defined by
+/// the issuer.
+pub type ReasonCode = u32;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DenyListEntry<C, K> {
+    pub id: DenyListEntryId,
+    pub component: Option<C>,
+    pub public_key: Option<K>,
+    pub reason_code: ReasonCode,
+    pub added_at_epoch: u64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DenyList<C: Ord, K: Ord> {
+    next_id: DenyListEntryId,
+    entries: BTreeMap<DenyListEntryId, DenyListEntry<C, K>>,
+    components: BTreeMap<C, DenyListEntryId>,
+    public_keys: BTreeMap<K, DenyListEntryId>,
+}
+
+impl<C: Ord + Clone, K: Ord + Clone> DenyList<C, K> {
+    pub fn new() -> Self {
+        Self {
+            next_id: 0,
+            entries: BTreeMap::new(),
+            components: BTreeMap::new(),
+            public_keys: BTreeMap::new(),
+        }
+    }
+
+    /// Adds an entry for the component and/or public key, returning its ID. Returns None, without using up an ID, if
+    /// neither is given or either is already denied.
+    pub fn insert(
+        &mut self,
+        component: Option<C>,
+        public_key: Option<K>,
+        reason_code: ReasonCode,
+        added_at_epoch: u64,
+    ) -> Option<DenyListEntryId> {
+        if component.is_none() && public_key.is_none() {
+            return None;
+        }
+        if component
+            .as_ref()
+            .is_some_and(|c| self.contains_component(c))
+            || public_key
+                .as_ref()
+                .is_some_and(|k| self.contains_public_key(k))
+        {
+            return None;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        if let Some(component) = &component {
+            self.components.insert(component.clone(), id);
+        }
+        if let Some(public_key) = &public_key {
+            self.public_keys.insert(public_key.clone(), id);
+        }
+        self.entries.insert(
+            id,
+            DenyListEntry {
+                id,
+                component,
+                public_key,
+                reason_code,
+                added_at_epoch,
+            },
+        );
+        Some(id)
+    }
+
+    pub fn insert_component(
+        &mut self,
+        component: C,
+        reason_code: ReasonCode,
+        added_at_epoch: u64,
+    ) -> Option<DenyListEntryId> {
+        self.insert(Some(component), None, reason_code, added_at_epoch)
+    }
+
+    pub fn insert_public_key(
+        &mut self,
+        public_key: K,
+        reason_code: ReasonCode,
+        added_at_epoch: u64,
+    ) -> Option<DenyListEntryId> {
+        self.insert(None, Some(public_key), reason_code, added_at_epoch)
+    }
+
+    pub fn contains_component(&self, component: &C) -> bool {
+        self.components.contains_key(component)
+    }
+
+    pub fn contains_public_key(&self, public_key: &K) -> bool {
+        self.public_keys.contains_key(public_key)
+    }
+
+    pub fn get(&self, id: DenyListEntryId) -> Option<&DenyListEntry<C, K>> {
+        self.entries.get(&id)
+    }
+
+    pub fn get_by_component(&self, component: &C) -> Option<&DenyListEntry<C, K>> {
+        self.components.get(component).and_then(|id| self.get(*id))
+    }
+
+    pub fn get_by_public_key(&self, public_key: &K) -> Option<&DenyListEntry<C, K>> {
+        self.public_keys
+            .get(public_key)
+            .and_then(|id| self.get(*id))
+    }
+
+    /// Removes the entry, including the component or public key it was paired with
+    pub fn remove(&mut self, id: DenyListEntryId) -> Option<DenyListEntry<C, K>> {
+        let entry = self.entries.remove(&id)?;
+        if let Some(component) = &entry.component {
+            self.components.remove(component);
+        }
+        if let Some(public_key) = &entry.public_key {
+            self.public_keys.remove(public_key);
+        }
+        Some(entry)
+    }
+
+    /// Removes the entry for the component, including any public key it was paired with
+    pub fn remove_by_component(&mut self, component: &C) -> Option<DenyListEntry<C, K>> {
+        let id = *self.components.get(component)?;
+        self.remove(id)
+    }
+
+    /// Removes the entry for the public key, including any component it was paired with
+    pub fn remove_by_public_key(&mut self, public_key: &K) -> Option<DenyListEntry<C, K>> {
+        let id = *self.public_keys.get(public_key)?;
+        self.remove(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns up to `limit` entries in ID order, starting after the given entry ID. Pass the ID of the last entry of
+    /// a page to fetch the next one.
+    pub fn list(
+        &self,
+        start_after: Option<DenyListEntryId>,
+        limit: usize,
+    ) -> Vec<DenyListEntry<C, K>> {
+        let start = start_after.map_or(0, |id| id.saturating_add(1));
+        self.entries
+            .range(start..)
+            .take(limit)
+            .map(|(_, entry)| entry.clone())
+            .collect()
+    }
+
+    /// Returns every entry in ID order
+    pub fn export(&self) -> Vec<DenyListEntry<C, K>> {
+        self.entries.values().cloned().collect()
+    }
+}
+
+impl<C: Ord + Clone, K: Ord + Clone> Default for DenyList<C, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestDenyList = DenyList<u8, u16>;
+
+    #[test]
+    fn it_finds_and_removes_entries_by_component_or_public_key() {
+        let mut list = TestDenyList::new();
+        let id = list.insert(Some(1), Some(100), 7, 10).unwrap();
+        assert_eq!(list.get_by_component(&1).unwrap().id, id);
+        assert_eq!(list.get_by_public_key(&100).unwrap().reason_code, 7);
+
+        let removed = list.remove_by_component(&1).unwrap();
+        assert_eq!(removed.public_key, Some(100));
+        assert!(!list.contains_public_key(&100));
+        assert!(list.is_empty());
+
+        list.insert(Some(2), Some(200), 7, 10).unwrap();
+        let removed = list.remove_by_public_key(&200).unwrap();
+        assert_eq!(removed.component, Some(2));
+        assert!(!list.contains_component(&2));
+        assert!(list.remove_by_public_key(&200).is_none());
+    }
+
+    #[test]
+    fn it_only_allocates_an_id_on_successful_insert() {
+        let mut list = TestDenyList::new();
+        assert_eq!(list.insert_component(1, 0, 0), Some(0));
+        assert_eq!(list.insert(Some(1), Some(100), 0, 0), None);
+        assert_eq!(list.insert(None, None, 0, 0), None);
+        assert_eq!(list.insert_public_key(100, 0, 0), Some(1));
+        assert_eq!(list.insert_public_key(100, 0, 0), None);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn it_lists_entries_in_pages() {
+        let mut list = TestDenyList::new();
+        for component in 0..5 {
+            list.insert_component(component, 1, u64::from(component))
+                .unwrap();
+        }
+        list.remove_by_component(&1);
+
+        let page = list.list(None, 2);
+        assert_eq!(page.iter().map(|e| e.id).collect::<Vec<_>>(), [0, 2]);
+        let page = list.list(Some(page.last().unwrap().id), 2);
+        assert_eq!(page.iter().map(|e| e.id).collect::<Vec<_>>(), [3, 4]);
+        assert!(list.list(Some(4), 2).is_empty());
+
+        let export = list.export();
+        assert_eq!(export.len(), 4);
+        assert_eq!(export[1].added_at_epoch, 2);
+    }
+}
@@ -7,12 +7,16 @@ pub fn main() {
     let badge = StableCoin::instantiate(
         addr,
         "100000000000000000000000000",
+        // Maximum supply, increase_supply cannot mint beyond this
+        "1000000000000000000000000000",
         "STC",
         Metadata("provider_name=StableCoin Inc."),
         8,
         // 32-byte public view key (hex)
         PublicKey("6c64d361a661900be82695786a3f9cdbae26f7b216c3d093bea101a309700379"),
         true,
+        // Optional StableCoinConfig, None uses the default fee schedule
+        None,
     );
     account.deposit(badge);
 }
@@ -0,0 +1,117 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use alloc::collections::BTreeSet;
+use core::fmt;
+
+/// A permission held by an admin badge. Admin methods are only callable by badges that hold the matching role.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum AdminRole {
+    /// Manages other admins: granting and revoking roles, issuing, rotating and revoking admin badges and setting mint
+    /// allowances
+    Super,
+    Minter,
+    Burner,
+    Compliance,
+    Pauser,
+    FeeManager,
+}
+
+impl AdminRole {
+    pub const ALL: [AdminRole; 6] = [
+        AdminRole::Super,
+        AdminRole::Minter,
+        AdminRole::Burner,
+        AdminRole::Compliance,
+        AdminRole::Pauser,
+        AdminRole::FeeManager,
+    ];
+
+    /// The component methods that require this role
+    pub fn methods(&self) -> &'static [&'static str] {
+        match self {
+            AdminRole::Super => &[
+                "create_new_admin",
+                "revoke_admin",
+                "rotate_admin",
+                "grant_admin_role",
+                "revoke_admin_role",
+                "set_mint_allowance",
+                "set_approval_threshold",
+                "set_config_timelock_delay",
+            ],
+            AdminRole::Minter => &["increase_supply", "withdraw"],
+            AdminRole::Burner => &["decrease_supply", "burn_utxo"],
+            AdminRole::Compliance => &[
+                "create_new_user",
+                "blacklist_user",
+                "batch_blacklist_users",
+                "remove_from_blacklist",
+                "freeze_user",
+                "unfreeze_user",
+                "add_to_deny_list",
+                "remove_from_deny_list",
+                "recall_revealed_tokens",
+                "batch_recall_revealed_tokens",
+                "seize_and_reissue",
+                "freeze_utxos",
+                "unfreeze_utxos",
+                "set_user_exchange_limit",
+                "batch_set_user_exchange_limit",
+                "set_user_wrapped_exchange_limit",
+                "set_user_redemption_limit",
+                "set_config_exchange_limit_window",
+                "set_config_default_exchange_limit",
+                "set_config_default_redemption_limit",
+            ],
+            AdminRole::Pauser => &["pause", "unpause"],
+            AdminRole::FeeManager => &[
+                "withdraw_fees",
+                "set_user_transfer_fee_override",
+                "set_user_wrapped_exchange_fee_override",
+                "set_config_transfer_fee_fixed",
+                "set_config_transfer_fee_percentage",
+                "set_config_transfer_fee_basis_points",
+                "set_config_transfer_fee_tiers",
+                "set_config_wrapped_exchange_fee_fixed",
+                "set_config_wrapped_exchange_fee_percentage",
+                "set_config_wrapped_exchange_fee_basis_points",
+                "set_config_wrapped_exchange_fee_tiers",
+                "set_config_redemption_fee",
+            ],
+        }
+    }
+}
+
+impl fmt::Display for AdminRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdminRole::Super => write!(f, "super"),
+            AdminRole::Minter => write!(f, "minter"),
+            AdminRole::Burner => write!(f, "burner"),
+            AdminRole::Compliance => write!(f, "compliance"),
+            AdminRole::Pauser => write!(f, "pauser"),
+            AdminRole::FeeManager => write!(f, "fee_manager"),
+        }
+    }
+}
+
+/// The mutable data stored on each admin badge
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct AdminBadgeData {
+    pub roles: BTreeSet<AdminRole>,
+}
+
+impl AdminBadgeData {
+    pub fn with_all_roles() -> Self {
+        Self {
+            roles: AdminRole::ALL.into_iter().collect(),
+        }
+    }
+
+    pub fn has_role(&self, role: AdminRole) -> bool {
+        self.roles.contains(&role)
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use crate::user_data::UserId;
+use alloc::string::String;
+
+/// What happened to a single entry of a batch compliance operation
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum BatchOutcome {
+    Applied,
+    /// The entry was left unchanged. The rest of the batch is still applied.
+    Skipped {
+        reason: String,
+    },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BatchEntryResult {
+    pub user_id: UserId,
+    pub outcome: BatchOutcome,
+}
+
+impl BatchEntryResult {
+    pub fn new(user_id: UserId, result: Result<(), String>) -> Self {
+        let outcome = match result {
+            Ok(()) => BatchOutcome::Applied,
+            Err(reason) => BatchOutcome::Skipped { reason },
+        };
+        Self { user_id, outcome }
+    }
+}
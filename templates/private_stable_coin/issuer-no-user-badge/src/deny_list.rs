@@ -0,0 +1,12 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::{ComponentAddress, RistrettoPublicKeyBytes};
+
+pub use stable_coin_deny_list::{DenyListEntryId, ReasonCode};
+
+/// Accounts and transaction signers that are blocked from the stable coin, whether or not they were ever issued a user
+/// badge
+pub type DenyList = stable_coin_deny_list::DenyList<ComponentAddress, RistrettoPublicKeyBytes>;
+pub type DenyListEntry =
+    stable_coin_deny_list::DenyListEntry<ComponentAddress, RistrettoPublicKeyBytes>;
@@ -24,9 +24,16 @@
 
 extern crate alloc;
 
+mod admin_roles;
+mod batch;
 mod config;
+mod deny_list;
+mod multisig;
+mod timelock;
 mod user_data;
 mod wrapped_exchange_token;
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::format;
 use alloc::string::String;
 use alloc::string::ToString;
@@ -49,35 +56,71 @@ mod template {
     use tari_template_lib::types::crypto::StealthValueProof;
 
     use super::*;
-    use crate::config::FeeSpec;
+    use crate::admin_roles::{AdminBadgeData, AdminRole};
+    use crate::batch::BatchEntryResult;
+    use crate::config::{BasisPointFee, FeeSpec, FeeTier, RoundingMode};
+    use crate::deny_list::{DenyList, DenyListEntry, DenyListEntryId, ReasonCode};
+    use crate::multisig::{AdminOperation, Proposal, ProposalId};
+    use crate::timelock::{QueuedAction, TimelockId, TimelockedAction};
     use crate::{config::StableCoinConfig, wrapped_exchange_token::WrappedExchangeToken};
 
     pub struct TariStableCoin {
         config: StableCoinConfig,
         token_vault: Vault,
+        fee_vault: Vault,
         user_auth_manager: ResourceManager,
         admin_auth_manager: ResourceManager,
         blacklisted_users: Vault,
         wrapped_token: Option<WrappedExchangeToken>,
         is_paused: bool,
+        /// The hard cap on `total_supply`, fixed at instantiation
+        max_supply: Amount,
+        /// All stable tokens issued, including those currently exchanged for wrapped tokens
+        total_supply: Amount,
+        /// The amount each admin badge may still mint
+        mint_allowances: BTreeMap<NonFungibleId, Amount>,
+        /// The number of admin badges that have been issued
+        admin_count: u32,
+        /// The number of distinct admin approvals required to execute a critical operation
+        approval_threshold: u32,
+        proposals: BTreeMap<ProposalId, Proposal>,
+        next_proposal_id: ProposalId,
+        /// Authorizes the component to update its own access rules
+        component_badge: Vault,
+        /// The admin badges holding each role, mirroring the role data stored on the badges
+        admin_roles: BTreeMap<AdminRole, BTreeSet<NonFungibleId>>,
+        timelock_queue: BTreeMap<TimelockId, QueuedAction>,
+        next_timelock_id: TimelockId,
+        deny_list: DenyList,
+        /// The IDs of all users that have been issued a badge
+        user_ids: BTreeSet<UserId>,
     }
 
     impl TariStableCoin {
         /// Instantiates a new stable coin component, returning a bucket containing an admin badge
+        #[allow(clippy::too_many_arguments)]
         pub fn instantiate(
             address_alloc: ComponentAddressAllocation,
             initial_token_supply: Amount,
+            max_supply: Amount,
             token_symbol: MaxString<8>,
             token_metadata: Metadata,
             divisibility: u8,
             view_key: RistrettoPublicKeyBytes,
             enable_wrapped_token: bool,
+            config: Option<StableCoinConfig>,
         ) -> Bucket {
             let provider_name = token_metadata.get("provider_name").unwrap_or_default();
 
-            let config = StableCoinConfig::default();
+            let config = config.unwrap_or_default();
+            config.validate();
+            assert!(
+                initial_token_supply <= max_supply,
+                "Initial token supply must not exceed the maximum supply"
+            );
 
-            // Create admin badge resource
+            // Create admin badge resource. The first badge holds every role, including super which can grant roles to
+            // other admins.
             let admin_badge = ResourceBuilder::non_fungible()
                 .with_metadata(metadata!(
                     "name" => "Stable Coin Admin Badge",
@@ -85,12 +128,30 @@ mod template {
                     "description" => format!("Admin badge for the {provider_name} stable coin"),
                     "admin_badge" => "true",
                 ))
-                .initial_supply(Some(NonFungibleId::from_u64(0)));
+                // Badges may only be minted, recalled and burned, and their roles changed, by this component
+                .mintable(rule!(component(address_alloc.get_address())))
+                .recallable(rule!(component(address_alloc.get_address())))
+                .burnable(rule!(component(address_alloc.get_address())))
+                .update_non_fungible_data(rule!(component(address_alloc.get_address())))
+                .initial_supply_with_data([(
+                    NonFungibleId::from_u64(0),
+                    (&Metadata::new(), &AdminBadgeData::with_all_roles()),
+                )]);
 
             // Create admin access rules
             let admin_resource = admin_badge.resource_address();
             let require_admin = rule!(resource(admin_resource));
 
+            // Create the badge that the component holds to authorize changes to its own access rules
+            let component_badge = ResourceBuilder::non_fungible()
+                .with_metadata(metadata!(
+                    "name" => "Stable Coin Component Badge",
+                    "provider_name" => provider_name,
+                ))
+                .with_owner_rule(OwnerRule::None)
+                .initial_supply(Some(NonFungibleId::from_u64(0)));
+            let component_badge_resource = component_badge.resource_address();
+
             // Create user badge resource
             let user_auth_resource = ResourceBuilder::non_fungible()
                 .with_metadata(metadata!(
@@ -114,6 +175,7 @@ mod template {
                 .with_view_key(view_key)
                 .with_divisibility(divisibility)
                 .initial_supply(initial_token_supply);
+            let token_resource = initial_tokens.resource_address();
 
             // Create tokens resource with initial supply
             let wrapped_token = if enable_wrapped_token {
@@ -130,60 +192,200 @@ mod template {
                 None
             };
 
-            // Create component access rules
-            let component_access_rules = AccessRules::new().default(require_admin);
-
-            // Create component
-            let _component = Component::new(Self {
+            let admin_badge_ids = BTreeSet::from([NonFungibleId::from_u64(0)]);
+            let stable_coin = Self {
                 config,
                 token_vault: Vault::from_bucket(initial_tokens),
+                fee_vault: Vault::new_empty(token_resource),
                 user_auth_manager: user_auth_resource.into(),
                 admin_auth_manager: admin_badge.resource_address().into(),
                 blacklisted_users: Vault::new_empty(user_auth_resource),
                 wrapped_token,
                 is_paused: false,
-            })
-            .with_address_allocation(address_alloc)
-            .with_access_rules(component_access_rules)
-            // Access is controlled by anyone with an admin badge
-            .with_owner_rule(OwnerRule::ByAccessRule(rule!(resource(admin_resource))))
-            .create();
+                max_supply,
+                total_supply: initial_token_supply,
+                // The founding admin may mint up to the maximum supply until another allowance is set
+                mint_allowances: BTreeMap::from([(
+                    NonFungibleId::from_u64(0),
+                    max_supply - initial_token_supply,
+                )]),
+                admin_count: 1,
+                approval_threshold: 1,
+                proposals: BTreeMap::new(),
+                next_proposal_id: 0,
+                component_badge: Vault::from_bucket(component_badge),
+                admin_roles: AdminRole::ALL
+                    .into_iter()
+                    .map(|role| (role, admin_badge_ids.clone()))
+                    .collect(),
+                timelock_queue: BTreeMap::new(),
+                next_timelock_id: 0,
+                deny_list: DenyList::new(),
+                user_ids: BTreeSet::new(),
+            };
+
+            // Create component access rules
+            let component_access_rules = stable_coin.access_rules();
+
+            // Create component
+            let _component = Component::new(stable_coin)
+                .with_address_allocation(address_alloc)
+                .with_access_rules(component_access_rules)
+                // The owner bypasses the method access rules, so only the component badge held by the component itself
+                // grants ownership. This lets the component update its access rules when roles are granted or revoked.
+                .with_owner_rule(OwnerRule::ByAccessRule(rule!(resource(
+                    component_badge_resource
+                ))))
+                .create();
 
             admin_badge
         }
 
-        /// Increase token supply by amount.
-        pub fn increase_supply(&mut self, amount: Amount) {
-            let new_tokens = self.token_vault_manager().mint_stealth(amount);
-            self.token_vault.deposit(new_tokens);
-
-            emit_event("increase_supply", metadata!("amount" => amount.to_string()));
+        /// Increase token supply by amount. The amount is deducted from the mint allowance of the admin badge in the
+        /// proof and may not take the total supply above the maximum supply.
+        pub fn increase_supply(&mut self, proof: Proof, amount: Amount) {
+            self.assert_no_approval_required();
+            self.assert_not_timelocked();
+            let badge = self.get_admin_badge_id(&proof);
+            self.mint_supply(badge, amount);
         }
 
         /// Decrease token supply by amount.
         pub fn decrease_supply(&mut self, amount: Amount) {
-            let tokens = self.token_vault.withdraw(amount);
-            tokens.burn();
+            self.assert_no_approval_required();
+            self.assert_not_timelocked();
+            self.burn_supply(amount);
+        }
+
+        /// Withdraws tokens from the issuer vault into the destination account, which must not be denied
+        pub fn withdraw(&mut self, amount: Amount, destination: ComponentAddress) {
+            self.assert_no_approval_required();
+            self.assert_not_denied(Some(&destination));
+            let bucket = self.withdraw_tokens(amount);
+            Self::deposit_into_account(destination, bucket);
+        }
+
+        pub fn deposit(&mut self, bucket: Bucket) {
+            self.assert_not_paused();
+            let amount = bucket.amount();
+            self.token_vault.deposit(bucket);
+            emit_event("deposit", metadata!("amount" => amount.to_string()));
+        }
+
+        /// Collect the configured transfer fee from a user-to-user transfer into the fee vault. The remaining tokens
+        /// are deposited into the recipient's account, which must not be denied.
+        pub fn transfer(
+            &mut self,
+            proof: Proof,
+            mut bucket: Bucket,
+            destination: ComponentAddress,
+        ) {
+            self.assert_not_paused();
+            self.assert_not_denied(Some(&destination));
+            assert_eq!(
+                bucket.resource_address(),
+                self.token_vault.resource_address(),
+                "The bucket must contain the same resource as the token vault"
+            );
+
+            assert!(
+                bucket.amount().is_positive(),
+                "The bucket must contain some tokens"
+            );
+
+            proof.assert_resource(self.user_auth_manager.resource_address());
+            let badges = proof.get_non_fungibles();
+            assert_eq!(badges.len(), 1, "The proof must contain exactly one badge");
+            let badge = badges.into_iter().next().unwrap();
+            let badge = self.user_auth_manager.get_non_fungible(&badge);
+            let user = badge.get_data::<UserData>();
+            let user_data = badge.get_mutable_data::<UserMutableData>();
+            user_data.assert_not_blacklisted(user.user_id);
+            user_data.assert_not_frozen(user.user_id);
+
+            let amount = bucket.amount();
+            let fee = self.transfer_fee(&user_data, amount);
+            assert!(fee < amount, "Insufficient funds to pay transfer fee");
+            if fee.is_positive() {
+                let fee_bucket = bucket.take(fee);
+                self.fee_vault.deposit(fee_bucket);
+            }
 
             emit_event(
-                "decrease_supply",
-                metadata!("revealed_burn_amount" => amount.to_string()),
+                "transfer",
+                metadata!(
+                    "user_id" => user.user_id.to_string(),
+                    "amount" => amount.to_string(),
+                    "fee" => fee.to_string(),
+                ),
             );
+
+            Self::deposit_into_account(destination, bucket);
         }
 
-        pub fn withdraw(&mut self, amount: Amount) -> Bucket {
-            let bucket = self.token_vault.withdraw(amount);
+        /// Executes a stealth transfer of UTXOs owned by the user in the proof, collecting the configured transfer fee
+        /// from the separate `fee` bucket into the fee vault. The values of stealth outputs are hidden, so the fee is
+        /// calculated on the revealed output amount, which is zero for a fully private transfer. `input` holds any
+        /// revealed funds spent by the statement. The revealed output, if any, is returned to be deposited into the
+        /// recipient's account.
+        pub fn stealth_transfer(
+            &mut self,
+            proof: Proof,
+            statement: StealthTransferStatement,
+            input: Option<Bucket>,
+            fee: Bucket,
+        ) -> Option<Bucket> {
+            self.assert_not_paused();
+            self.assert_not_denied(None);
+
+            proof.assert_resource(self.user_auth_manager.resource_address());
+            let badges = proof.get_non_fungibles();
+            assert_eq!(badges.len(), 1, "The proof must contain exactly one badge");
+            let badge = badges.into_iter().next().unwrap();
+            let badge = self.user_auth_manager.get_non_fungible(&badge);
+            let user = badge.get_data::<UserData>();
+            let user_data = badge.get_mutable_data::<UserMutableData>();
+            user_data.assert_not_blacklisted(user.user_id);
+            user_data.assert_not_frozen(user.user_id);
+
+            assert_eq!(
+                fee.resource_address(),
+                self.token_vault.resource_address(),
+                "The fee bucket must contain the same resource as the token vault"
+            );
+            let revealed_amount = statement.revealed_output_amount();
+            let fee_amount = self.transfer_fee(&user_data, revealed_amount);
+            assert_eq!(
+                fee.amount(),
+                fee_amount,
+                "The fee bucket must contain the transfer fee of {fee_amount}"
+            );
+            self.fee_vault.deposit(fee);
+
+            let stealth_outputs = statement.stealth_outputs().len();
+            // The spend conditions of the inputs are checked against the transaction signer, so only the owner of the
+            // UTXOs can spend them through this method
+            let output = self
+                .token_vault_manager()
+                .stealth_transfer_with_opt_input_bucket(statement, input);
+
             emit_event(
-                "withdraw",
-                metadata!("amount_withdrawn" => bucket.amount().to_string()),
+                "stealth_transfer",
+                metadata!(
+                    "user_id" => user.user_id.to_string(),
+                    "stealth_outputs" => stealth_outputs.to_string(),
+                    "revealed_amount" => revealed_amount.to_string(),
+                    "fee" => fee_amount.to_string(),
+                ),
             );
-            bucket
+
+            output
         }
 
-        pub fn deposit(&mut self, bucket: Bucket) {
-            let amount = bucket.amount();
-            self.token_vault.deposit(bucket);
-            emit_event("deposit", metadata!("amount" => amount.to_string()));
+        /// Withdraw collected transfer fees
+        pub fn withdraw_fees(&mut self, amount: Amount) -> Bucket {
+            self.assert_no_approval_required();
+            self.withdraw_fee_tokens(amount)
         }
 
         /// Allow the user to exchange their tokens for wrapped tokens
@@ -192,6 +394,8 @@ mod template {
             proof: Proof,
             mut bucket: Bucket,
         ) -> Bucket {
+            self.assert_not_paused();
+            self.assert_not_denied(None);
             assert_eq!(
                 bucket.resource_address(),
                 self.token_vault.resource_address(),
@@ -207,38 +411,48 @@ mod template {
             let badges = proof.get_non_fungibles();
             assert_eq!(badges.len(), 1, "The proof must contain exactly one badge");
             let badge = badges.into_iter().next().unwrap();
-            let badge = self.user_auth_manager.get_non_fungible(&badge);
+            let mut badge = self.user_auth_manager.get_non_fungible(&badge);
             let user = badge.get_data::<UserData>();
-            let user_data = badge.get_mutable_data::<UserMutableData>();
+            let mut user_data = badge.get_mutable_data::<UserMutableData>();
+            user_data.assert_not_blacklisted(user.user_id);
+            user_data.assert_not_frozen(user.user_id);
 
             let amount = bucket.amount();
+            user_data.roll_exchange_window(
+                Consensus::current_epoch(),
+                self.config.exchange_limit_window_epochs,
+            );
             assert!(
-                amount <= user_data.wrapped_exchange_limit,
+                amount <= user_data.remaining_wrapped_exchange_limit(),
                 "Exchange limit exceeded"
             );
+            user_data.record_wrapped_exchange(amount);
+            badge.set_mutable_data(&user_data);
 
-            self.set_user_wrapped_exchange_limit(
-                user.user_id,
-                user_data.wrapped_exchange_limit - amount,
-            );
-
-            let fee = self.config.wrapped_exchange_fee.calculate_fee(amount);
+            let fee = user_data
+                .wrapped_exchange_fee_override
+                .as_ref()
+                .unwrap_or(&self.config.wrapped_exchange_fee)
+                .calculate_fee(amount);
             let new_amount = amount
                 .checked_sub(fee)
                 .expect("Insufficient funds to pay exchange fee");
-            let fee_bucket = bucket.take(fee);
+            if fee.is_positive() {
+                let fee_bucket = bucket.take(fee);
+                self.token_vault.deposit(fee_bucket);
+            }
             bucket.burn();
 
-            self.token_vault.deposit(fee_bucket);
-
             let wrapped_tokens = self.wrapped_token().manager().mint_fungible(new_amount);
 
             emit_event(
                 "exchange_stable_for_wrapped_tokens",
                 metadata!(
                     "user_id" => user.user_id.to_string(),
-                    "amount" => amount.to_string(),
+                    "gross_amount" => amount.to_string(),
+                    "net_amount" => new_amount.to_string(),
                     "fee" => fee.to_string(),
+                    "remaining_limit" => user_data.remaining_wrapped_exchange_limit().to_string(),
                 ),
             );
 
@@ -251,6 +465,8 @@ mod template {
             proof: Proof,
             wrapped_bucket: Bucket,
         ) -> Bucket {
+            self.assert_not_paused();
+            self.assert_not_denied(None);
             assert!(
                 !wrapped_bucket.amount().is_zero(),
                 "The bucket must contain some tokens"
@@ -267,23 +483,48 @@ mod template {
             let badges = proof.get_non_fungibles();
             assert_eq!(badges.len(), 1, "The proof must contain exactly one badge");
             let badge = badges.into_iter().next().unwrap();
-            let badge = self.user_auth_manager.get_non_fungible(&badge);
+            let mut badge = self.user_auth_manager.get_non_fungible(&badge);
             let user = badge.get_data::<UserData>();
+            let mut user_data = badge.get_mutable_data::<UserMutableData>();
+            user_data.assert_not_blacklisted(user.user_id);
+            user_data.assert_not_frozen(user.user_id);
 
             let amount = wrapped_bucket.amount();
+            user_data.roll_exchange_window(
+                Consensus::current_epoch(),
+                self.config.exchange_limit_window_epochs,
+            );
+            assert!(
+                amount <= user_data.remaining_redemption_limit(),
+                "Redemption limit exceeded"
+            );
+            user_data.record_redemption(amount);
+            badge.set_mutable_data(&user_data);
+
+            let fee = self.config.redemption_fee.calculate_fee(amount);
+            let new_amount = amount
+                .checked_sub(fee)
+                .expect("Insufficient funds to pay redemption fee");
 
             // Burn the wrapped tokens
             wrapped_bucket.burn();
 
-            // Mint tokens
-            let tokens = self.token_vault.get_resource_manager().mint_stealth(amount);
+            // Mint tokens, keeping the fee in the token vault
+            let mut tokens = self.token_vault.get_resource_manager().mint_stealth(amount);
+
+            if fee.is_positive() {
+                let fee_bucket = tokens.take(fee);
+                self.token_vault.deposit(fee_bucket);
+            }
 
             emit_event(
                 "exchange_wrapped_for_stable_tokens",
                 metadata!(
                         "user_id" => user.user_id.to_string(),
-                        "amount" => amount.to_string(),
-                        "fee" => 0.to_string(),
+                        "gross_amount" => amount.to_string(),
+                        "net_amount" => new_amount.to_string(),
+                        "fee" => fee.to_string(),
+                        "remaining_limit" => user_data.remaining_redemption_limit().to_string(),
                 ),
             );
 
@@ -291,32 +532,30 @@ mod template {
         }
 
         pub fn recall_revealed_tokens(&mut self, user_id: UserId, amount: Amount) {
-            // Fetch the user badge
-            let badge = self.user_auth_manager.get_non_fungible(&user_id.into());
-            let user = badge.get_data::<UserData>();
-
-            let account = user.user_account.get_state::<Account>();
-
-            let vault = account
-                .get_vault_by_resource(&self.token_vault.resource_address())
-                .expect("The user's account does not have a vault for the stable coin resource");
-            let vault_id = vault.vault_id();
-
-            let bucket = self
-                .token_vault_manager()
-                .recall_fungible_amount(vault_id, amount);
-            self.token_vault.deposit(bucket);
+            self.assert_no_approval_required();
+            self.recall_tokens(user_id, amount)
+                .unwrap_or_else(|reason| panic!("{}", reason));
+        }
 
-            emit_event(
-                "recall_tokens",
-                metadata!(
-                        "user_id" => user_id.to_string(),
-                        "revealed_amount" => amount.to_string(),
-                ),
-            );
+        /// Recalls a revealed amount from each user. Users without enough revealed balance are skipped and reported in
+        /// the results.
+        pub fn batch_recall_revealed_tokens(
+            &mut self,
+            recalls: Vec<(UserId, Amount)>,
+        ) -> Vec<BatchEntryResult> {
+            self.assert_no_approval_required();
+            recalls
+                .into_iter()
+                .map(|(user_id, amount)| {
+                    let result = self.recall_tokens(user_id, amount);
+                    Self::batch_entry_result("recall_tokens", user_id, result)
+                })
+                .collect()
         }
 
         pub fn burn_utxo(&mut self, utxo: UtxoId, value_proof: StealthValueProof) {
+            self.assert_not_paused();
+            self.total_supply = self.total_supply.saturating_sub(value_proof.value);
             self.token_vault_manager()
                 .burn_utxo(utxo, Some(value_proof));
             emit_event(
@@ -328,13 +567,44 @@ mod template {
             );
         }
 
+        /// Seizes a user's funds for lawful asset recovery. The user's revealed balance is recalled and the given
+        /// stealth UTXOs (typically frozen beforehand) are burned using admin-held value proofs, as in `burn_utxo`.
+        /// The seized amount is reissued in the returned bucket, to be deposited into the recovery account, leaving the
+        /// total supply unchanged. Every event carries the case reference.
+        pub fn seize_and_reissue(
+            &mut self,
+            user_id: UserId,
+            utxos: Vec<(UtxoId, StealthValueProof)>,
+            case_reference: String,
+        ) -> Bucket {
+            self.assert_no_approval_required();
+            self.seize_tokens(user_id, utxos, case_reference)
+        }
+
         pub fn create_new_admin(&mut self, employee_id: String) -> Bucket {
-            let id = NonFungibleId::random();
-            emit_event("create_new_admin", metadata!("admin_id" => id.to_string()));
-            let mut metadata = Metadata::new();
-            metadata.insert("employee_id", employee_id);
-            self.admin_auth_manager
-                .mint_non_fungible(id, &metadata, &())
+            self.assert_no_approval_required();
+            self.mint_admin_badge(employee_id)
+        }
+
+        /// Recalls and burns an admin badge, removing its roles and mint allowance. The last remaining admin and the
+        /// last super admin cannot be revoked.
+        pub fn revoke_admin(
+            &mut self,
+            admin_account: ComponentAddress,
+            admin_badge_id: NonFungibleId,
+        ) {
+            self.assert_no_approval_required();
+            self.revoke_admin_badge(admin_account, admin_badge_id);
+        }
+
+        /// Issues a replacement admin badge with the same roles and mint allowance, then recalls and burns the old one
+        pub fn rotate_admin(
+            &mut self,
+            admin_account: ComponentAddress,
+            admin_badge_id: NonFungibleId,
+        ) -> Bucket {
+            self.assert_no_approval_required();
+            self.rotate_admin_badge(admin_account, admin_badge_id)
         }
 
         pub fn create_new_user(
@@ -342,7 +612,9 @@ mod template {
             user_id: UserId,
             user_account: ComponentAddress,
         ) -> Bucket {
+            self.assert_not_denied(Some(&user_account));
             let epoch = Consensus::current_epoch();
+            self.user_ids.insert(user_id);
             let badge = self.user_auth_manager.mint_non_fungible(
                 user_id.into(),
                 &UserData {
@@ -352,7 +624,14 @@ mod template {
                 },
                 &UserMutableData {
                     is_blacklisted: false,
+                    is_frozen: false,
                     wrapped_exchange_limit: self.config.default_exchange_limit,
+                    wrapped_exchanged_in_window: Amount::ZERO,
+                    redemption_limit: self.config.default_redemption_limit,
+                    redeemed_in_window: Amount::ZERO,
+                    exchange_window_start_epoch: epoch,
+                    transfer_fee_override: None,
+                    wrapped_exchange_fee_override: None,
                 },
             );
             emit_event(
@@ -363,51 +642,52 @@ mod template {
         }
 
         pub fn set_user_exchange_limit(&mut self, user_id: UserId, limit: Amount) {
-            assert!(limit.is_positive(), "Exchange limit must be positive");
-            let non_fungible_id: NonFungibleId = user_id.into();
-
-            let user_badge = self.user_auth_manager.get_non_fungible(&non_fungible_id);
-            let user_data = user_badge.get_mutable_data::<UserMutableData>();
-            self.user_auth_manager.update_non_fungible_data(
-                non_fungible_id,
-                &UserMutableData {
-                    wrapped_exchange_limit: limit,
-                    ..user_data
-                },
-            );
-
-            let admin = CallerContext::transaction_signer_public_key();
-            emit_event(
-                "set_user_exchange_limit",
-                metadata!(
-                        "user_id" => user_id.to_string(),
-                        "limit" => limit.to_string(),
-                        "admin" => admin.to_string(),
-                ),
-            );
+            self.try_set_user_exchange_limit(user_id, limit)
+                .unwrap_or_else(|reason| panic!("{}", reason));
         }
 
-        pub fn blacklist_user(&mut self, vault_id: VaultId, user_id: UserId) {
-            let non_fungible_id: NonFungibleId = user_id.into();
+        /// Sets the exchange limit of each user. Entries with a non-positive limit are skipped and reported in the
+        /// results.
+        pub fn batch_set_user_exchange_limit(
+            &mut self,
+            limits: Vec<(UserId, Amount)>,
+        ) -> Vec<BatchEntryResult> {
+            limits
+                .into_iter()
+                .map(|(user_id, limit)| {
+                    let result = self.try_set_user_exchange_limit(user_id, limit);
+                    Self::batch_entry_result("set_user_exchange_limit", user_id, result)
+                })
+                .collect()
+        }
 
-            let recalled = self
-                .user_auth_manager
-                .recall_non_fungible(vault_id, non_fungible_id.clone());
-            let user_badge = self.user_auth_manager.get_non_fungible(&non_fungible_id);
-            let user_data = user_badge.get_mutable_data::<UserMutableData>();
-            self.user_auth_manager.update_non_fungible_data(
-                non_fungible_id,
-                &UserMutableData {
-                    is_blacklisted: true,
-                    ..user_data
-                },
-            );
+        /// Recalls the user's badge from their account into the blacklist vault. Optionally also recalls the revealed
+        /// stable coin balance of the account and freezes the given UTXOs that are known to belong to the user.
+        pub fn blacklist_user(
+            &mut self,
+            user_id: UserId,
+            recall_revealed_balance: bool,
+            utxos_to_freeze: Vec<UtxoId>,
+        ) {
+            self.try_blacklist_user(user_id, recall_revealed_balance, utxos_to_freeze)
+                .unwrap_or_else(|reason| panic!("{}", reason));
+        }
 
-            self.blacklisted_users.deposit(recalled);
-            emit_event(
-                "blacklist_user",
-                metadata!("user_id" => user_id.to_string()),
-            );
+        /// Blacklists each user in turn, optionally recalling their revealed balances. Users that are already
+        /// blacklisted or whose accounts no longer hold their badge are skipped and reported in the results.
+        pub fn batch_blacklist_users(
+            &mut self,
+            user_ids: Vec<UserId>,
+            recall_revealed_balance: bool,
+        ) -> Vec<BatchEntryResult> {
+            user_ids
+                .into_iter()
+                .map(|user_id| {
+                    let result =
+                        self.try_blacklist_user(user_id, recall_revealed_balance, Vec::new());
+                    Self::batch_entry_result("blacklist_user", user_id, result)
+                })
+                .collect()
         }
 
         pub fn remove_from_blacklist(&mut self, user_id: UserId) -> Bucket {
@@ -428,6 +708,88 @@ mod template {
             user_badge_bucket
         }
 
+        /// Freezes a user's account. A frozen user cannot transfer or exchange tokens until they are unfrozen, and the
+        /// revealed token vault in their account, if any, is frozen so that it cannot send or receive tokens.
+        pub fn freeze_user(&mut self, user_id: UserId) {
+            self.set_user_frozen(user_id, true);
+        }
+
+        pub fn unfreeze_user(&mut self, user_id: UserId) {
+            self.set_user_frozen(user_id, false);
+        }
+
+        /// Adds accounts and transaction signer public keys to the deny list with the given reason code. Denied accounts
+        /// cannot be issued a user badge, and transactions signed by a denied key cannot transfer or exchange tokens.
+        /// Entries that are already denied are ignored.
+        pub fn add_to_deny_list(
+            &mut self,
+            components: Vec<ComponentAddress>,
+            public_keys: Vec<RistrettoPublicKeyBytes>,
+            reason_code: ReasonCode,
+        ) {
+            let epoch = Consensus::current_epoch();
+            for component in components {
+                if let Some(id) = self
+                    .deny_list
+                    .insert_component(component, reason_code, epoch)
+                {
+                    emit_event(
+                        "deny_list.added",
+                        metadata!(
+                            "entry_id" => id.to_string(),
+                            "component" => component.to_string(),
+                            "reason_code" => reason_code.to_string(),
+                        ),
+                    );
+                }
+            }
+            for public_key in public_keys {
+                if let Some(id) = self
+                    .deny_list
+                    .insert_public_key(public_key, reason_code, epoch)
+                {
+                    emit_event(
+                        "deny_list.added",
+                        metadata!(
+                            "entry_id" => id.to_string(),
+                            "public_key" => public_key.to_string(),
+                            "reason_code" => reason_code.to_string(),
+                        ),
+                    );
+                }
+            }
+        }
+
+        /// Removes accounts and public keys from the deny list. Entries that are not denied are ignored.
+        pub fn remove_from_deny_list(
+            &mut self,
+            components: Vec<ComponentAddress>,
+            public_keys: Vec<RistrettoPublicKeyBytes>,
+        ) {
+            for component in components {
+                if let Some(entry) = self.deny_list.remove_by_component(&component) {
+                    emit_event(
+                        "deny_list.removed",
+                        metadata!(
+                            "entry_id" => entry.id.to_string(),
+                            "component" => component.to_string(),
+                        ),
+                    );
+                }
+            }
+            for public_key in public_keys {
+                if let Some(entry) = self.deny_list.remove_by_public_key(&public_key) {
+                    emit_event(
+                        "deny_list.removed",
+                        metadata!(
+                            "entry_id" => entry.id.to_string(),
+                            "public_key" => public_key.to_string(),
+                        ),
+                    );
+                }
+            }
+        }
+
         pub fn set_user_wrapped_exchange_limit(&mut self, user_id: UserId, new_limit: Amount) {
             let mut badge = self.user_auth_manager.get_non_fungible(&user_id.into());
             let mut user_data = badge.get_mutable_data::<UserMutableData>();
@@ -442,7 +804,43 @@ mod template {
             );
         }
 
+        pub fn set_user_redemption_limit(&mut self, user_id: UserId, new_limit: Amount) {
+            assert!(new_limit.is_positive(), "Redemption limit must be positive");
+            let mut badge = self.user_auth_manager.get_non_fungible(&user_id.into());
+            let mut user_data = badge.get_mutable_data::<UserMutableData>();
+            user_data.set_redemption_limit(new_limit);
+            badge.set_mutable_data(&user_data);
+            emit_event(
+                "set_user_redemption_limit",
+                [
+                    ("user_id", user_id.to_string()),
+                    ("limit", new_limit.to_string()),
+                ],
+            );
+        }
+
+        pub fn set_user_transfer_fee_override(&mut self, user_id: UserId, fee: Option<FeeSpec>) {
+            self.assert_not_timelocked();
+            if let Some(fee) = &fee {
+                fee.validate();
+            }
+            self.update_user_transfer_fee_override(user_id, fee);
+        }
+
+        pub fn set_user_wrapped_exchange_fee_override(
+            &mut self,
+            user_id: UserId,
+            fee: Option<FeeSpec>,
+        ) {
+            self.assert_not_timelocked();
+            if let Some(fee) = &fee {
+                fee.validate();
+            }
+            self.update_user_wrapped_exchange_fee_override(user_id, fee);
+        }
+
         pub fn set_config_transfer_fee_fixed(&mut self, new_fee: Amount) {
+            self.assert_not_timelocked();
             emit_event(
                 "config.set_transfer_fee_fixed",
                 metadata!(
@@ -454,6 +852,7 @@ mod template {
         }
 
         pub fn set_config_transfer_fee_percentage(&mut self, new_fee_perc: u8) {
+            self.assert_not_timelocked();
             assert!(
                 new_fee_perc <= 100,
                 "Percentage fee must be between 0 and 100"
@@ -468,54 +867,1398 @@ mod template {
             self.config.transfer_fee = FeeSpec::Percentage(new_fee_perc);
         }
 
-        pub fn pause(&mut self, proof: Proof) {
-            proof.assert_resource(self.admin_auth_manager.resource_address());
-            // Could also add a check for a specific admin badge ID if desired
-            let badge = proof
-                .get_non_fungibles()
-                .first()
-                .expect("Proof must contain an admin badge")
-                .to_string();
-            self.is_paused = true;
+        pub fn set_config_transfer_fee_basis_points(
+            &mut self,
+            bps: u16,
+            rounding: RoundingMode,
+            min_fee: Option<Amount>,
+            max_fee: Option<Amount>,
+        ) {
+            self.assert_not_timelocked();
+            let new_fee = FeeSpec::BasisPoints(BasisPointFee {
+                bps,
+                rounding,
+                min_fee,
+                max_fee,
+            });
+            new_fee.validate();
             emit_event(
-                "admin.paused",
+                "config.set_transfer_fee_basis_points",
                 metadata!(
-                    "tx_signer" => CallerContext::transaction_signer_public_key().to_string(),
-                    "admin_badge" => badge
+                    "old_transfer_fee" => self.config.transfer_fee.to_string(),
+                    "new_transfer_fee" => new_fee.to_string(),
                 ),
             );
+            self.config.transfer_fee = new_fee;
         }
 
-        pub fn freeze_utxos(&self, utxos: Vec<UtxoId>) {
+        /// Replaces the transfer fee with the given tier table
+        pub fn set_config_transfer_fee_tiers(&mut self, tiers: Vec<FeeTier>) {
+            self.assert_not_timelocked();
+            let num_tiers = tiers.len();
+            let new_fee = FeeSpec::Tiered(tiers);
+            new_fee.validate();
             emit_event(
-                "admin.freeze_utxos",
+                "config.set_transfer_fee_tiers",
                 metadata!(
-                    "tx_signer" => CallerContext::transaction_signer_public_key().to_string(),
-                    "num_utxos" => utxos.len().to_string(),
+                    "old_transfer_fee" => self.config.transfer_fee.to_string(),
+                    "new_transfer_fee" => new_fee.to_string(),
+                    "num_tiers" => num_tiers.to_string(),
                 ),
             );
-            self.token_vault_manager().freeze_utxos(utxos);
+            self.config.transfer_fee = new_fee;
         }
 
-        pub fn unfreeze_utxos(&self, utxos: Vec<UtxoId>) {
+        pub fn set_config_wrapped_exchange_fee_fixed(&mut self, new_fee: Amount) {
+            self.assert_not_timelocked();
             emit_event(
-                "admin.unfreeze_utxos",
+                "config.set_wrapped_exchange_fee_fixed",
                 metadata!(
-                    "tx_signer" => CallerContext::transaction_signer_public_key().to_string(),
-                    "num_utxos" => utxos.len().to_string(),
+                    "old_wrapped_exchange_fee" => self.config.wrapped_exchange_fee.to_string(),
+                    "new_wrapped_exchange_fee" => new_fee.to_string(),
                 ),
             );
-            self.token_vault_manager().unfreeze_utxos(utxos);
+            self.config.wrapped_exchange_fee = FeeSpec::Fixed(new_fee);
         }
 
-        fn token_vault_manager(&self) -> ResourceManager {
-            self.token_vault.get_resource_manager()
+        pub fn set_config_wrapped_exchange_fee_percentage(&mut self, new_fee_perc: u8) {
+            self.assert_not_timelocked();
+            assert!(
+                new_fee_perc <= 100,
+                "Percentage fee must be between 0 and 100"
+            );
+            emit_event(
+                "config.set_wrapped_exchange_fee_percentage",
+                metadata!(
+                    "old_wrapped_exchange_fee" => self.config.wrapped_exchange_fee.to_string(),
+                    "new_wrapped_exchange_fee" => format!("{new_fee_perc}%"),
+                ),
+            );
+            self.config.wrapped_exchange_fee = FeeSpec::Percentage(new_fee_perc);
         }
 
-        fn wrapped_token(&self) -> &WrappedExchangeToken {
-            self.wrapped_token
-                .as_ref()
-                .expect("Wrapped token is not enabled")
-        }
+        pub fn set_config_wrapped_exchange_fee_basis_points(
+            &mut self,
+            bps: u16,
+            rounding: RoundingMode,
+            min_fee: Option<Amount>,
+            max_fee: Option<Amount>,
+        ) {
+            self.assert_not_timelocked();
+            let new_fee = FeeSpec::BasisPoints(BasisPointFee {
+                bps,
+                rounding,
+                min_fee,
+                max_fee,
+            });
+            new_fee.validate();
+            emit_event(
+                "config.set_wrapped_exchange_fee_basis_points",
+                metadata!(
+                    "old_wrapped_exchange_fee" => self.config.wrapped_exchange_fee.to_string(),
+                    "new_wrapped_exchange_fee" => new_fee.to_string(),
+                ),
+            );
+            self.config.wrapped_exchange_fee = new_fee;
+        }
+
+        /// Sets the number of epochs after which each user's wrapped exchange allowance is reset
+        pub fn set_config_exchange_limit_window(&mut self, window_epochs: u64) {
+            self.assert_not_timelocked();
+            assert!(
+                window_epochs > 0,
+                "Exchange limit window must be at least one epoch"
+            );
+            self.update_exchange_limit_window(window_epochs);
+        }
+
+        /// Replaces the wrapped exchange fee with the given tier table
+        pub fn set_config_wrapped_exchange_fee_tiers(&mut self, tiers: Vec<FeeTier>) {
+            self.assert_not_timelocked();
+            let num_tiers = tiers.len();
+            let new_fee = FeeSpec::Tiered(tiers);
+            new_fee.validate();
+            emit_event(
+                "config.set_wrapped_exchange_fee_tiers",
+                metadata!(
+                    "old_wrapped_exchange_fee" => self.config.wrapped_exchange_fee.to_string(),
+                    "new_wrapped_exchange_fee" => new_fee.to_string(),
+                    "num_tiers" => num_tiers.to_string(),
+                ),
+            );
+            self.config.wrapped_exchange_fee = new_fee;
+        }
+
+        /// Sets the exchange limit given to users created after this call. Existing users keep their current limit.
+        pub fn set_config_default_exchange_limit(&mut self, new_limit: Amount) {
+            self.assert_not_timelocked();
+            assert!(new_limit.is_positive(), "Exchange limit must be positive");
+            self.update_default_exchange_limit(new_limit);
+        }
+
+        pub fn set_config_redemption_fee(&mut self, new_fee: FeeSpec) {
+            self.assert_not_timelocked();
+            new_fee.validate();
+            emit_event(
+                "config.set_redemption_fee",
+                metadata!(
+                    "old_redemption_fee" => self.config.redemption_fee.to_string(),
+                    "new_redemption_fee" => new_fee.to_string(),
+                ),
+            );
+            self.config.redemption_fee = new_fee;
+        }
+
+        /// Sets the redemption limit given to users created after this call. Existing users keep their current limit.
+        pub fn set_config_default_redemption_limit(&mut self, new_limit: Amount) {
+            self.assert_not_timelocked();
+            assert!(new_limit.is_positive(), "Redemption limit must be positive");
+            self.update_default_redemption_limit(new_limit);
+        }
+
+        /// Sets the delay for timelocked actions. Once a delay is set, changing it is itself timelocked.
+        pub fn set_config_timelock_delay(&mut self, delay_epochs: u64) {
+            self.assert_not_timelocked();
+            self.update_timelock_delay(delay_epochs);
+        }
+
+        /// Sets the amount that the given admin badge may mint with `increase_supply`
+        pub fn set_mint_allowance(&mut self, admin_badge_id: NonFungibleId, allowance: Amount) {
+            self.assert_not_timelocked();
+            self.update_mint_allowance(admin_badge_id, allowance);
+        }
+
+        /// Sets the number of distinct admin approvals required for critical operations. Once above one, this can only
+        /// be changed through a proposal.
+        pub fn set_approval_threshold(&mut self, threshold: u32) {
+            self.assert_no_approval_required();
+            self.update_approval_threshold(threshold);
+        }
+
+        /// Proposes a critical operation, counting as the proposer's approval. The proposal can be approved by other
+        /// admins until `expires_at_epoch`.
+        pub fn propose(
+            &mut self,
+            proof: Proof,
+            operation: AdminOperation,
+            expires_at_epoch: u64,
+        ) -> ProposalId {
+            let badge = self.get_admin_badge_id(&proof);
+            assert!(
+                self.approval_threshold > 1,
+                "Proposals are not required when the approval threshold is one"
+            );
+            self.assert_admin_role(&badge, operation.required_role());
+            assert!(
+                expires_at_epoch > Consensus::current_epoch(),
+                "Proposal expiry must be in the future"
+            );
+
+            let proposal_id = self.next_proposal_id;
+            self.next_proposal_id += 1;
+            emit_event(
+                "multisig.proposed",
+                metadata!(
+                    "proposal_id" => proposal_id.to_string(),
+                    "operation" => operation.to_string(),
+                    "proposer" => badge.to_string(),
+                    "expires_at_epoch" => expires_at_epoch.to_string(),
+                ),
+            );
+            self.proposals.insert(
+                proposal_id,
+                Proposal::new(operation, badge, expires_at_epoch),
+            );
+            proposal_id
+        }
+
+        /// Approves a proposal with a distinct admin badge holding the operation's role or the super role. The operation
+        /// is executed by the approval that reaches the threshold, returning a bucket for operations that produce one
+        /// (`Withdraw`, `WithdrawFees` and `CreateNewAdmin`).
+        pub fn approve_proposal(
+            &mut self,
+            proof: Proof,
+            proposal_id: ProposalId,
+        ) -> Option<Bucket> {
+            let badge = self.get_admin_badge_id(&proof);
+            let proposal = self
+                .proposals
+                .get_mut(&proposal_id)
+                .expect("Proposal not found");
+            assert!(
+                !proposal.is_expired(Consensus::current_epoch()),
+                "Proposal has expired"
+            );
+            let required_role = proposal.operation.required_role();
+            let approver_roles = self
+                .admin_auth_manager
+                .get_non_fungible(&badge)
+                .get_mutable_data::<AdminBadgeData>();
+            assert!(
+                approver_roles.has_role(required_role) || approver_roles.has_role(AdminRole::Super),
+                "The {required_role} or super role is required to approve this proposal"
+            );
+            proposal.approve(badge.clone());
+            let num_approvals = proposal.num_approvals();
+            emit_event(
+                "multisig.approved",
+                metadata!(
+                    "proposal_id" => proposal_id.to_string(),
+                    "admin_badge" => badge.to_string(),
+                    "approvals" => num_approvals.to_string(),
+                    "threshold" => self.approval_threshold.to_string(),
+                ),
+            );
+
+            if num_approvals < self.approval_threshold {
+                return None;
+            }
+
+            let proposal = self.proposals.remove(&proposal_id).unwrap();
+            emit_event(
+                "multisig.executed",
+                metadata!(
+                    "proposal_id" => proposal_id.to_string(),
+                    "operation" => proposal.operation.to_string(),
+                ),
+            );
+            match proposal.operation {
+                AdminOperation::IncreaseSupply { amount } => {
+                    if self.is_timelocked() {
+                        self.enqueue_action(
+                            TimelockedAction::IncreaseSupply { amount },
+                            proposal.proposer,
+                            num_approvals,
+                        );
+                    } else {
+                        self.mint_supply(proposal.proposer, amount);
+                    }
+                    None
+                }
+                AdminOperation::DecreaseSupply { amount } => {
+                    if self.is_timelocked() {
+                        self.enqueue_action(
+                            TimelockedAction::DecreaseSupply { amount },
+                            proposal.proposer,
+                            num_approvals,
+                        );
+                    } else {
+                        self.burn_supply(amount);
+                    }
+                    None
+                }
+                AdminOperation::Withdraw { amount } => Some(self.withdraw_tokens(amount)),
+                AdminOperation::WithdrawFees { amount } => Some(self.withdraw_fee_tokens(amount)),
+                AdminOperation::CreateNewAdmin { employee_id } => {
+                    Some(self.mint_admin_badge(employee_id))
+                }
+                AdminOperation::RecallRevealedTokens { user_id, amount } => {
+                    self.recall_tokens(user_id, amount)
+                        .unwrap_or_else(|reason| panic!("{}", reason));
+                    None
+                }
+                AdminOperation::SeizeAndReissue {
+                    user_id,
+                    utxos,
+                    case_reference,
+                } => Some(self.seize_tokens(user_id, utxos, case_reference)),
+                AdminOperation::SetApprovalThreshold { threshold } => {
+                    self.update_approval_threshold(threshold);
+                    None
+                }
+                AdminOperation::RevokeAdmin {
+                    admin_account,
+                    admin_badge_id,
+                } => {
+                    self.revoke_admin_badge(admin_account, admin_badge_id);
+                    None
+                }
+                AdminOperation::RotateAdmin {
+                    admin_account,
+                    admin_badge_id,
+                } => Some(self.rotate_admin_badge(admin_account, admin_badge_id)),
+            }
+        }
+
+        /// Cancels a proposal. The proposer may cancel at any time, other admins only once it has expired.
+        pub fn cancel_proposal(&mut self, proof: Proof, proposal_id: ProposalId) {
+            let badge = self.get_admin_badge_id(&proof);
+            let proposal = self
+                .proposals
+                .remove(&proposal_id)
+                .expect("Proposal not found");
+            assert!(
+                proposal.proposer == badge || proposal.is_expired(Consensus::current_epoch()),
+                "Only the proposer may cancel a proposal before it expires"
+            );
+            emit_event(
+                "multisig.cancelled",
+                metadata!(
+                    "proposal_id" => proposal_id.to_string(),
+                    "operation" => proposal.operation.to_string(),
+                    "admin_badge" => badge.to_string(),
+                ),
+            );
+        }
+
+        /// Queues a timelocked action. It can be executed once the configured delay has passed and cancelled until then.
+        /// Supply changes that require multiple admin approvals are queued by `approve_proposal` instead.
+        pub fn queue_action(&mut self, proof: Proof, action: TimelockedAction) -> TimelockId {
+            let badge = self.get_admin_badge_id(&proof);
+            assert!(self.is_timelocked(), "Timelock is not enabled");
+            self.assert_admin_role(&badge, action.required_role());
+            if action.is_supply_change() {
+                self.assert_no_approval_required();
+            }
+            action.validate();
+            self.enqueue_action(action, badge, 1)
+        }
+
+        /// Executes a queued action once its execution epoch has been reached
+        pub fn execute_action(&mut self, proof: Proof, timelock_id: TimelockId) {
+            let badge = self.get_admin_badge_id(&proof);
+            let queued = self
+                .timelock_queue
+                .get(&timelock_id)
+                .expect("Timelocked action not found");
+            self.assert_admin_role(&badge, queued.action.required_role());
+            assert!(
+                !queued.action.is_supply_change() || queued.approvals >= self.approval_threshold,
+                "This action was queued with {} admin approvals but {} are now required, submit it as a proposal",
+                queued.approvals,
+                self.approval_threshold
+            );
+            assert!(
+                queued.is_executable(Consensus::current_epoch()),
+                "Timelocked action cannot be executed before epoch {}",
+                queued.execute_at_epoch
+            );
+
+            let queued = self.timelock_queue.remove(&timelock_id).unwrap();
+            emit_event(
+                "timelock.executed",
+                metadata!(
+                    "timelock_id" => timelock_id.to_string(),
+                    "action" => queued.action.to_string(),
+                    "admin_badge" => badge.to_string(),
+                ),
+            );
+            match queued.action {
+                TimelockedAction::IncreaseSupply { amount } => {
+                    self.mint_supply(queued.queued_by, amount)
+                }
+                TimelockedAction::DecreaseSupply { amount } => self.burn_supply(amount),
+                TimelockedAction::SetTransferFee { fee } => {
+                    emit_event(
+                        "config.set_transfer_fee",
+                        metadata!(
+                            "old_transfer_fee" => self.config.transfer_fee.to_string(),
+                            "new_transfer_fee" => fee.to_string(),
+                        ),
+                    );
+                    self.config.transfer_fee = fee;
+                }
+                TimelockedAction::SetWrappedExchangeFee { fee } => {
+                    emit_event(
+                        "config.set_wrapped_exchange_fee",
+                        metadata!(
+                            "old_wrapped_exchange_fee" => self.config.wrapped_exchange_fee.to_string(),
+                            "new_wrapped_exchange_fee" => fee.to_string(),
+                        ),
+                    );
+                    self.config.wrapped_exchange_fee = fee;
+                }
+                TimelockedAction::SetRedemptionFee { fee } => {
+                    emit_event(
+                        "config.set_redemption_fee",
+                        metadata!(
+                            "old_redemption_fee" => self.config.redemption_fee.to_string(),
+                            "new_redemption_fee" => fee.to_string(),
+                        ),
+                    );
+                    self.config.redemption_fee = fee;
+                }
+                TimelockedAction::SetTimelockDelay { delay_epochs } => {
+                    self.update_timelock_delay(delay_epochs)
+                }
+                TimelockedAction::SetUserTransferFeeOverride { user_id, fee } => {
+                    self.update_user_transfer_fee_override(user_id, fee)
+                }
+                TimelockedAction::SetUserWrappedExchangeFeeOverride { user_id, fee } => {
+                    self.update_user_wrapped_exchange_fee_override(user_id, fee)
+                }
+                TimelockedAction::SetExchangeLimitWindow { window_epochs } => {
+                    self.update_exchange_limit_window(window_epochs)
+                }
+                TimelockedAction::SetDefaultExchangeLimit { limit } => {
+                    self.update_default_exchange_limit(limit)
+                }
+                TimelockedAction::SetDefaultRedemptionLimit { limit } => {
+                    self.update_default_redemption_limit(limit)
+                }
+                TimelockedAction::SetMintAllowance {
+                    admin_badge_id,
+                    allowance,
+                } => self.update_mint_allowance(admin_badge_id, allowance),
+            }
+        }
+
+        /// Cancels a queued action before its execution epoch
+        pub fn cancel_action(&mut self, proof: Proof, timelock_id: TimelockId) {
+            let badge = self.get_admin_badge_id(&proof);
+            let queued = self
+                .timelock_queue
+                .remove(&timelock_id)
+                .expect("Timelocked action not found");
+            self.assert_admin_role(&badge, queued.action.required_role());
+            assert!(
+                !queued.is_executable(Consensus::current_epoch()),
+                "Timelocked action can no longer be cancelled"
+            );
+            emit_event(
+                "timelock.cancelled",
+                metadata!(
+                    "timelock_id" => timelock_id.to_string(),
+                    "action" => queued.action.to_string(),
+                    "admin_badge" => badge.to_string(),
+                ),
+            );
+        }
+
+        /// Returns the queued timelocked actions, giving users notice of pending changes
+        pub fn get_queued_actions(&self) -> BTreeMap<TimelockId, QueuedAction> {
+            self.timelock_queue.clone()
+        }
+
+        /// Grants a role to an admin badge, allowing it to call the methods that require the role
+        pub fn grant_admin_role(&mut self, admin_badge_id: NonFungibleId, role: AdminRole) {
+            let mut badge = self.admin_auth_manager.get_non_fungible(&admin_badge_id);
+            let mut badge_data = badge.get_mutable_data::<AdminBadgeData>();
+            assert!(
+                badge_data.roles.insert(role),
+                "Admin badge {admin_badge_id} already has the {role} role"
+            );
+            badge.set_mutable_data(&badge_data);
+            self.admin_roles
+                .entry(role)
+                .or_default()
+                .insert(admin_badge_id.clone());
+            self.update_access_rules();
+
+            emit_event(
+                "admin.role_granted",
+                metadata!(
+                    "tx_signer" => CallerContext::transaction_signer_public_key().to_string(),
+                    "admin_badge" => admin_badge_id.to_string(),
+                    "role" => role.to_string(),
+                ),
+            );
+        }
+
+        /// Revokes a role from an admin badge. The super role cannot be revoked from the last admin that holds it.
+        pub fn revoke_admin_role(&mut self, admin_badge_id: NonFungibleId, role: AdminRole) {
+            let mut badge = self.admin_auth_manager.get_non_fungible(&admin_badge_id);
+            let mut badge_data = badge.get_mutable_data::<AdminBadgeData>();
+            assert!(
+                badge_data.roles.remove(&role),
+                "Admin badge {admin_badge_id} does not have the {role} role"
+            );
+            let holders = self.admin_roles.entry(role).or_default();
+            holders.remove(&admin_badge_id);
+            assert!(
+                role != AdminRole::Super || !holders.is_empty(),
+                "Cannot revoke the super role from the last super admin"
+            );
+            badge.set_mutable_data(&badge_data);
+            self.update_access_rules();
+
+            emit_event(
+                "admin.role_revoked",
+                metadata!(
+                    "tx_signer" => CallerContext::transaction_signer_public_key().to_string(),
+                    "admin_badge" => admin_badge_id.to_string(),
+                    "role" => role.to_string(),
+                ),
+            );
+        }
+
+        pub fn pause(&mut self, proof: Proof) {
+            let badge = self.get_admin_badge_id(&proof);
+            assert!(!self.is_paused, "Token is already paused");
+            self.is_paused = true;
+            emit_event(
+                "admin.paused",
+                metadata!(
+                    "tx_signer" => CallerContext::transaction_signer_public_key().to_string(),
+                    "admin_badge" => badge.to_string()
+                ),
+            );
+        }
+
+        pub fn unpause(&mut self, proof: Proof) {
+            let badge = self.get_admin_badge_id(&proof);
+            assert!(self.is_paused, "Token is not paused");
+            self.is_paused = false;
+            emit_event(
+                "admin.unpaused",
+                metadata!(
+                    "tx_signer" => CallerContext::transaction_signer_public_key().to_string(),
+                    "admin_badge" => badge.to_string()
+                ),
+            );
+        }
+
+        pub fn freeze_utxos(&self, utxos: Vec<UtxoId>) {
+            emit_event(
+                "admin.freeze_utxos",
+                metadata!(
+                    "tx_signer" => CallerContext::transaction_signer_public_key().to_string(),
+                    "num_utxos" => utxos.len().to_string(),
+                ),
+            );
+            self.token_vault_manager().freeze_utxos(utxos);
+        }
+
+        pub fn unfreeze_utxos(&self, utxos: Vec<UtxoId>) {
+            emit_event(
+                "admin.unfreeze_utxos",
+                metadata!(
+                    "tx_signer" => CallerContext::transaction_signer_public_key().to_string(),
+                    "num_utxos" => utxos.len().to_string(),
+                ),
+            );
+            self.token_vault_manager().unfreeze_utxos(utxos);
+        }
+
+        pub fn get_config(&self) -> StableCoinConfig {
+            self.config.clone()
+        }
+
+        pub fn is_paused(&self) -> bool {
+            self.is_paused
+        }
+
+        /// All stable tokens issued, including those currently exchanged for wrapped tokens
+        pub fn total_supply(&self) -> Amount {
+            self.total_supply
+        }
+
+        /// The revealed balance of the component's token vault
+        pub fn reserve_balance(&self) -> Amount {
+            self.token_vault.balance()
+        }
+
+        pub fn wrapped_token_resource(&self) -> Option<ResourceAddress> {
+            self.wrapped_token
+                .as_ref()
+                .map(|wrapped_token| wrapped_token.resource_address())
+        }
+
+        pub fn get_user(&self, user_id: UserId) -> (UserData, UserMutableData) {
+            let badge = self.user_auth_manager.get_non_fungible(&user_id.into());
+            (badge.get_data(), badge.get_mutable_data())
+        }
+
+        /// Returns the users whose badges are held in the blacklist vault
+        pub fn list_blacklisted(&self) -> Vec<UserId> {
+            self.blacklisted_users
+                .get_non_fungibles()
+                .into_iter()
+                .map(|badge| badge.get_data::<UserData>().user_id)
+                .collect()
+        }
+
+        /// Returns up to `limit` deny list entries, starting after the given entry ID
+        pub fn list_deny_list(
+            &self,
+            start_after: Option<DenyListEntryId>,
+            limit: u32,
+        ) -> Vec<DenyListEntry> {
+            self.deny_list.list(start_after, limit as usize)
+        }
+
+        /// Returns the full deny list, including the reason code and epoch of each entry, for audits
+        pub fn export_deny_list(&self) -> Vec<DenyListEntry> {
+            self.deny_list.export()
+        }
+
+        fn assert_no_approval_required(&self) {
+            assert!(
+                self.approval_threshold <= 1,
+                "This operation requires {} admin approvals, submit it as a proposal",
+                self.approval_threshold
+            );
+        }
+
+        fn is_timelocked(&self) -> bool {
+            self.config.timelock_delay_epochs > 0
+        }
+
+        fn assert_not_timelocked(&self) {
+            assert!(
+                !self.is_timelocked(),
+                "This operation is timelocked, queue it with queue_action"
+            );
+        }
+
+        fn enqueue_action(
+            &mut self,
+            action: TimelockedAction,
+            queued_by: NonFungibleId,
+            approvals: u32,
+        ) -> TimelockId {
+            let timelock_id = self.next_timelock_id;
+            self.next_timelock_id += 1;
+            let queued_at_epoch = Consensus::current_epoch();
+            let execute_at_epoch = queued_at_epoch + self.config.timelock_delay_epochs;
+            emit_event(
+                "timelock.queued",
+                metadata!(
+                    "timelock_id" => timelock_id.to_string(),
+                    "action" => action.to_string(),
+                    "queued_by" => queued_by.to_string(),
+                    "execute_at_epoch" => execute_at_epoch.to_string(),
+                ),
+            );
+            self.timelock_queue.insert(
+                timelock_id,
+                QueuedAction {
+                    action,
+                    queued_by,
+                    approvals,
+                    queued_at_epoch,
+                    execute_at_epoch,
+                },
+            );
+            timelock_id
+        }
+
+        fn update_timelock_delay(&mut self, delay_epochs: u64) {
+            emit_event(
+                "config.set_timelock_delay",
+                metadata!(
+                    "old_delay_epochs" => self.config.timelock_delay_epochs.to_string(),
+                    "new_delay_epochs" => delay_epochs.to_string(),
+                ),
+            );
+            self.config.timelock_delay_epochs = delay_epochs;
+        }
+
+        fn update_user_transfer_fee_override(&mut self, user_id: UserId, fee: Option<FeeSpec>) {
+            let mut badge = self.user_auth_manager.get_non_fungible(&user_id.into());
+            let mut user_data = badge.get_mutable_data::<UserMutableData>();
+            emit_event(
+                "set_user_transfer_fee_override",
+                metadata!(
+                    "user_id" => user_id.to_string(),
+                    "old_transfer_fee" => fee_override_to_string(user_data.transfer_fee_override.as_ref()),
+                    "new_transfer_fee" => fee_override_to_string(fee.as_ref()),
+                ),
+            );
+            user_data.transfer_fee_override = fee;
+            badge.set_mutable_data(&user_data);
+        }
+
+        fn update_user_wrapped_exchange_fee_override(
+            &mut self,
+            user_id: UserId,
+            fee: Option<FeeSpec>,
+        ) {
+            let mut badge = self.user_auth_manager.get_non_fungible(&user_id.into());
+            let mut user_data = badge.get_mutable_data::<UserMutableData>();
+            emit_event(
+                "set_user_wrapped_exchange_fee_override",
+                metadata!(
+                    "user_id" => user_id.to_string(),
+                    "old_wrapped_exchange_fee" => fee_override_to_string(user_data.wrapped_exchange_fee_override.as_ref()),
+                    "new_wrapped_exchange_fee" => fee_override_to_string(fee.as_ref()),
+                ),
+            );
+            user_data.wrapped_exchange_fee_override = fee;
+            badge.set_mutable_data(&user_data);
+        }
+
+        fn update_exchange_limit_window(&mut self, window_epochs: u64) {
+            emit_event(
+                "config.set_exchange_limit_window",
+                metadata!(
+                    "old_window_epochs" => self.config.exchange_limit_window_epochs.to_string(),
+                    "new_window_epochs" => window_epochs.to_string(),
+                ),
+            );
+            self.config.exchange_limit_window_epochs = window_epochs;
+        }
+
+        fn update_default_exchange_limit(&mut self, new_limit: Amount) {
+            emit_event(
+                "config.set_default_exchange_limit",
+                metadata!(
+                    "old_default_exchange_limit" => self.config.default_exchange_limit.to_string(),
+                    "new_default_exchange_limit" => new_limit.to_string(),
+                ),
+            );
+            self.config.default_exchange_limit = new_limit;
+        }
+
+        fn update_default_redemption_limit(&mut self, new_limit: Amount) {
+            emit_event(
+                "config.set_default_redemption_limit",
+                metadata!(
+                    "old_default_redemption_limit" => self.config.default_redemption_limit.to_string(),
+                    "new_default_redemption_limit" => new_limit.to_string(),
+                ),
+            );
+            self.config.default_redemption_limit = new_limit;
+        }
+
+        fn update_mint_allowance(&mut self, admin_badge_id: NonFungibleId, allowance: Amount) {
+            let old_allowance = self
+                .mint_allowances
+                .insert(admin_badge_id.clone(), allowance)
+                .unwrap_or_default();
+            emit_event(
+                "admin.set_mint_allowance",
+                metadata!(
+                    "admin_badge" => admin_badge_id.to_string(),
+                    "old_allowance" => old_allowance.to_string(),
+                    "new_allowance" => allowance.to_string(),
+                ),
+            );
+        }
+
+        fn assert_admin_role(&self, badge: &NonFungibleId, role: AdminRole) {
+            assert!(
+                self.admin_auth_manager
+                    .get_non_fungible(badge)
+                    .get_mutable_data::<AdminBadgeData>()
+                    .has_role(role),
+                "The {role} role is required for this operation"
+            );
+        }
+
+        fn mint_supply(&mut self, badge: NonFungibleId, amount: Amount) {
+            self.assert_not_paused();
+            let total_supply = self
+                .total_supply
+                .checked_add(amount)
+                .filter(|total| *total <= self.max_supply)
+                .expect("Maximum supply exceeded");
+            let remaining_allowance = self
+                .mint_allowances
+                .get(&badge)
+                .and_then(|allowance| allowance.checked_sub(amount))
+                .expect("Mint allowance exceeded");
+            self.mint_allowances
+                .insert(badge.clone(), remaining_allowance);
+            self.total_supply = total_supply;
+
+            let new_tokens = self.token_vault_manager().mint_stealth(amount);
+            self.token_vault.deposit(new_tokens);
+
+            emit_event(
+                "increase_supply",
+                metadata!(
+                    "amount" => amount.to_string(),
+                    "admin_badge" => badge.to_string(),
+                    "remaining_allowance" => remaining_allowance.to_string(),
+                    "total_supply" => total_supply.to_string(),
+                ),
+            );
+        }
+
+        fn burn_supply(&mut self, amount: Amount) {
+            let tokens = self.token_vault.withdraw(amount);
+            tokens.burn();
+            self.total_supply = self.total_supply.saturating_sub(amount);
+
+            emit_event(
+                "decrease_supply",
+                metadata!("revealed_burn_amount" => amount.to_string()),
+            );
+        }
+
+        fn withdraw_tokens(&mut self, amount: Amount) -> Bucket {
+            self.assert_not_paused();
+            let bucket = self.token_vault.withdraw(amount);
+            emit_event(
+                "withdraw",
+                metadata!("amount_withdrawn" => bucket.amount().to_string()),
+            );
+            bucket
+        }
+
+        fn withdraw_fee_tokens(&mut self, amount: Amount) -> Bucket {
+            let bucket = self.fee_vault.withdraw(amount);
+            emit_event(
+                "withdraw_fees",
+                metadata!("amount_withdrawn" => bucket.amount().to_string()),
+            );
+            bucket
+        }
+
+        fn recall_tokens(&mut self, user_id: UserId, amount: Amount) -> Result<(), String> {
+            self.check_user_exists(user_id)?;
+            // Fetch the user badge
+            let badge = self.user_auth_manager.get_non_fungible(&user_id.into());
+            let user = badge.get_data::<UserData>();
+
+            let vault =
+                Self::find_account_vault(&user.user_account, &self.token_vault.resource_address())
+                    .ok_or_else(|| {
+                        "The user's account does not have a vault for the stable coin resource"
+                            .to_string()
+                    })?;
+            let balance = vault.balance();
+            if balance < amount {
+                return Err(format!(
+                    "User {} has a revealed balance of {}, which is less than {}",
+                    user_id, balance, amount
+                ));
+            }
+
+            let bucket = self
+                .token_vault_manager()
+                .recall_fungible_amount(vault.vault_id(), amount);
+            self.token_vault.deposit(bucket);
+
+            emit_event(
+                "recall_tokens",
+                metadata!(
+                        "user_id" => user_id.to_string(),
+                        "revealed_amount" => amount.to_string(),
+                ),
+            );
+            Ok(())
+        }
+
+        fn try_blacklist_user(
+            &mut self,
+            user_id: UserId,
+            recall_revealed_balance: bool,
+            utxos_to_freeze: Vec<UtxoId>,
+        ) -> Result<(), String> {
+            self.check_user_exists(user_id)?;
+            let non_fungible_id: NonFungibleId = user_id.into();
+            let user_badge = self.user_auth_manager.get_non_fungible(&non_fungible_id);
+            let user_data = user_badge.get_mutable_data::<UserMutableData>();
+            if user_data.is_blacklisted {
+                return Err(format!("User {} is already blacklisted", user_id));
+            }
+            let user = user_badge.get_data::<UserData>();
+
+            let badge_vault = Self::find_account_vault(
+                &user.user_account,
+                &self.user_auth_manager.resource_address(),
+            )
+            .filter(|vault| vault.get_non_fungible_ids().contains(&non_fungible_id))
+            .ok_or_else(|| "The user's account does not hold their user badge".to_string())?;
+            let recalled = self
+                .user_auth_manager
+                .recall_non_fungible(badge_vault.vault_id(), non_fungible_id.clone());
+            self.user_auth_manager.update_non_fungible_data(
+                non_fungible_id,
+                &UserMutableData {
+                    is_blacklisted: true,
+                    ..user_data
+                },
+            );
+
+            self.blacklisted_users.deposit(recalled);
+
+            let mut revealed_amount = Amount::zero();
+            if recall_revealed_balance
+                && let Some(vault) = Self::find_account_vault(
+                    &user.user_account,
+                    &self.token_vault.resource_address(),
+                )
+            {
+                let bucket = self.token_vault_manager().recall_all(vault.vault_id());
+                revealed_amount = bucket.amount();
+                self.token_vault.deposit(bucket);
+            }
+            let num_utxos = utxos_to_freeze.len();
+            if num_utxos > 0 {
+                self.token_vault_manager().freeze_utxos(utxos_to_freeze);
+            }
+
+            emit_event(
+                "blacklist_user",
+                metadata!(
+                    "user_id" => user_id.to_string(),
+                    "revealed_amount" => revealed_amount.to_string(),
+                    "num_utxos" => num_utxos.to_string(),
+                ),
+            );
+            Ok(())
+        }
+
+        fn try_set_user_exchange_limit(
+            &mut self,
+            user_id: UserId,
+            limit: Amount,
+        ) -> Result<(), String> {
+            if !limit.is_positive() {
+                return Err("Exchange limit must be positive".to_string());
+            }
+            self.check_user_exists(user_id)?;
+            let non_fungible_id: NonFungibleId = user_id.into();
+
+            let user_badge = self.user_auth_manager.get_non_fungible(&non_fungible_id);
+            let user_data = user_badge.get_mutable_data::<UserMutableData>();
+            self.user_auth_manager.update_non_fungible_data(
+                non_fungible_id,
+                &UserMutableData {
+                    wrapped_exchange_limit: limit,
+                    ..user_data
+                },
+            );
+
+            let admin = CallerContext::transaction_signer_public_key();
+            emit_event(
+                "set_user_exchange_limit",
+                metadata!(
+                        "user_id" => user_id.to_string(),
+                        "limit" => limit.to_string(),
+                        "admin" => admin.to_string(),
+                ),
+            );
+            Ok(())
+        }
+
+        fn check_user_exists(&self, user_id: UserId) -> Result<(), String> {
+            if self.user_ids.contains(&user_id) {
+                Ok(())
+            } else {
+                Err(format!("User {} does not exist", user_id))
+            }
+        }
+
+        /// Emits an event for a skipped batch entry, so that indexers see every entry of the batch
+        fn batch_entry_result(
+            operation: &str,
+            user_id: UserId,
+            result: Result<(), String>,
+        ) -> BatchEntryResult {
+            if let Err(reason) = &result {
+                emit_event(
+                    "batch.skipped",
+                    metadata!(
+                        "operation" => operation.to_string(),
+                        "user_id" => user_id.to_string(),
+                        "reason" => reason.clone(),
+                    ),
+                );
+            }
+            BatchEntryResult::new(user_id, result)
+        }
+
+        fn set_user_frozen(&mut self, user_id: UserId, is_frozen: bool) {
+            let mut badge = self.user_auth_manager.get_non_fungible(&user_id.into());
+            let mut user_data = badge.get_mutable_data::<UserMutableData>();
+            if is_frozen {
+                user_data.assert_not_frozen(user_id);
+            } else {
+                assert!(user_data.is_frozen, "User {} is not frozen", user_id);
+            }
+            user_data.is_frozen = is_frozen;
+            badge.set_mutable_data(&user_data);
+
+            // Users can withdraw from their account without going through transfer, so their token vault is frozen too
+            let user = badge.get_data::<UserData>();
+            if let Some(vault) =
+                Self::find_account_vault(&user.user_account, &self.token_vault.resource_address())
+            {
+                let vault_id = vault.vault_id();
+                if is_frozen {
+                    self.token_vault_manager().freeze_vault(vault_id);
+                } else {
+                    self.token_vault_manager().unfreeze_vault(vault_id);
+                }
+            }
+
+            emit_event(
+                if is_frozen {
+                    "freeze_user"
+                } else {
+                    "unfreeze_user"
+                },
+                metadata!("user_id" => user_id.to_string()),
+            );
+        }
+
+        fn seize_tokens(
+            &mut self,
+            user_id: UserId,
+            utxos: Vec<(UtxoId, StealthValueProof)>,
+            case_reference: String,
+        ) -> Bucket {
+            assert!(!case_reference.is_empty(), "A case reference is required");
+            let badge = self.user_auth_manager.get_non_fungible(&user_id.into());
+            let user = badge.get_data::<UserData>();
+
+            let mut revealed_amount = Amount::zero();
+            if let Some(vault) =
+                Self::find_account_vault(&user.user_account, &self.token_vault.resource_address())
+            {
+                let bucket = self.token_vault_manager().recall_all(vault.vault_id());
+                revealed_amount = bucket.amount();
+                self.token_vault.deposit(bucket);
+            }
+
+            let mut burned_amount = Amount::zero();
+            for (utxo, value_proof) in utxos {
+                let value = value_proof.value;
+                burned_amount = burned_amount
+                    .checked_add(value)
+                    .expect("Seized amount overflowed");
+                self.token_vault_manager()
+                    .burn_utxo(utxo, Some(value_proof));
+                emit_event(
+                    "seize.burn_utxo",
+                    metadata!(
+                        "case_reference" => case_reference.clone(),
+                        "user_id" => user_id.to_string(),
+                        "utxo_id" => utxo.to_string(),
+                        "amount" => value.to_string(),
+                    ),
+                );
+            }
+            // The burned stealth value is minted again, so the total supply is unchanged
+            if burned_amount.is_positive() {
+                let reissued = self.token_vault_manager().mint_stealth(burned_amount);
+                self.token_vault.deposit(reissued);
+            }
+
+            let seized_amount = revealed_amount
+                .checked_add(burned_amount)
+                .expect("Seized amount overflowed");
+            assert!(
+                seized_amount.is_positive(),
+                "User {} has no funds to seize",
+                user_id
+            );
+            let bucket = self.token_vault.withdraw(seized_amount);
+
+            emit_event(
+                "seize_and_reissue",
+                metadata!(
+                    "case_reference" => case_reference,
+                    "user_id" => user_id.to_string(),
+                    "revealed_amount" => revealed_amount.to_string(),
+                    "burned_amount" => burned_amount.to_string(),
+                ),
+            );
+            bucket
+        }
+
+        /// The transfer fee charged to a user for `amount`, using the user's override if one is set
+        fn transfer_fee(&self, user_data: &UserMutableData, amount: Amount) -> Amount {
+            user_data
+                .transfer_fee_override
+                .as_ref()
+                .unwrap_or(&self.config.transfer_fee)
+                .calculate_fee(amount)
+        }
+
+        fn deposit_into_account(account: ComponentAddress, bucket: Bucket) {
+            ComponentManager::get(account).call::<_, (), _>("deposit", args![bucket]);
+        }
+
+        fn find_account_vault(
+            account: &ComponentManager,
+            resource_address: &ResourceAddress,
+        ) -> Option<Vault> {
+            account
+                .get_state::<Account>()
+                .get_vault_by_resource(resource_address)
+                .cloned()
+        }
+
+        fn mint_admin_badge(&mut self, employee_id: String) -> Bucket {
+            let id = NonFungibleId::random();
+            self.admin_count += 1;
+            emit_event("create_new_admin", metadata!("admin_id" => id.to_string()));
+            let mut metadata = Metadata::new();
+            metadata.insert("employee_id", employee_id);
+            self.admin_auth_manager
+                .mint_non_fungible(id, &metadata, &AdminBadgeData::default())
+        }
+
+        fn revoke_admin_badge(
+            &mut self,
+            admin_account: ComponentAddress,
+            admin_badge_id: NonFungibleId,
+        ) {
+            assert!(
+                self.admin_count > 1,
+                "Cannot revoke the last remaining admin"
+            );
+            assert!(
+                self.approval_threshold < self.admin_count,
+                "Cannot revoke an admin while the approval threshold requires all {} admins",
+                self.admin_count
+            );
+            self.retire_admin_badge(admin_account, &admin_badge_id);
+            self.cancel_admin_badge_actions(&admin_badge_id);
+            assert!(
+                self.admin_roles
+                    .get(&AdminRole::Super)
+                    .is_some_and(|holders| !holders.is_empty()),
+                "Cannot revoke the last super admin"
+            );
+            self.admin_count -= 1;
+            self.update_access_rules();
+
+            emit_event(
+                "admin.revoked",
+                metadata!(
+                    "tx_signer" => CallerContext::transaction_signer_public_key().to_string(),
+                    "admin_badge" => admin_badge_id.to_string(),
+                ),
+            );
+        }
+
+        fn rotate_admin_badge(
+            &mut self,
+            admin_account: ComponentAddress,
+            admin_badge_id: NonFungibleId,
+        ) -> Bucket {
+            let old_badge = self.admin_auth_manager.get_non_fungible(&admin_badge_id);
+            let metadata = old_badge.get_data::<Metadata>();
+            let allowance = self.mint_allowances.get(&admin_badge_id).copied();
+            let badge_data = self.retire_admin_badge(admin_account, &admin_badge_id);
+
+            let new_id = NonFungibleId::random();
+            for role in &badge_data.roles {
+                self.admin_roles
+                    .entry(*role)
+                    .or_default()
+                    .insert(new_id.clone());
+            }
+            if let Some(allowance) = allowance {
+                self.mint_allowances.insert(new_id.clone(), allowance);
+            }
+            self.reassign_admin_badge_actions(&admin_badge_id, &new_id);
+            self.update_access_rules();
+
+            emit_event(
+                "admin.rotated",
+                metadata!(
+                    "tx_signer" => CallerContext::transaction_signer_public_key().to_string(),
+                    "old_admin_badge" => admin_badge_id.to_string(),
+                    "new_admin_badge" => new_id.to_string(),
+                ),
+            );
+            self.admin_auth_manager
+                .mint_non_fungible(new_id, &metadata, &badge_data)
+        }
+
+        /// Recalls and burns an admin badge, removing it from the role holders and mint allowances. Returns the roles the
+        /// badge held.
+        fn retire_admin_badge(
+            &mut self,
+            admin_account: ComponentAddress,
+            admin_badge_id: &NonFungibleId,
+        ) -> AdminBadgeData {
+            let badge_data = self
+                .admin_auth_manager
+                .get_non_fungible(admin_badge_id)
+                .get_mutable_data::<AdminBadgeData>();
+            for role in &badge_data.roles {
+                if let Some(holders) = self.admin_roles.get_mut(role) {
+                    holders.remove(admin_badge_id);
+                }
+            }
+            self.mint_allowances.remove(admin_badge_id);
+
+            let vault_id = Self::find_account_vault(
+                &ComponentManager::get(admin_account),
+                &self.admin_auth_manager.resource_address(),
+            )
+            .expect("The account does not hold an admin badge")
+            .vault_id();
+            self.admin_auth_manager
+                .recall_non_fungible(vault_id, admin_badge_id.clone())
+                .burn();
+            badge_data
+        }
+
+        /// Cancels the proposals and queued actions of a revoked admin badge and withdraws its pending approvals
+        fn cancel_admin_badge_actions(&mut self, admin_badge_id: &NonFungibleId) {
+            self.proposals.retain(|proposal_id, proposal| {
+                if proposal.proposer != *admin_badge_id {
+                    proposal.approvals.remove(admin_badge_id);
+                    return true;
+                }
+                emit_event(
+                    "multisig.cancelled",
+                    metadata!(
+                        "proposal_id" => proposal_id.to_string(),
+                        "operation" => proposal.operation.to_string(),
+                        "admin_badge" => admin_badge_id.to_string(),
+                    ),
+                );
+                false
+            });
+            self.timelock_queue.retain(|timelock_id, queued| {
+                if queued.queued_by != *admin_badge_id {
+                    return true;
+                }
+                emit_event(
+                    "timelock.cancelled",
+                    metadata!(
+                        "timelock_id" => timelock_id.to_string(),
+                        "action" => queued.action.to_string(),
+                        "admin_badge" => admin_badge_id.to_string(),
+                    ),
+                );
+                false
+            });
+        }
+
+        /// Hands the proposals, approvals and queued actions of a rotated admin badge over to its replacement
+        fn reassign_admin_badge_actions(&mut self, old_id: &NonFungibleId, new_id: &NonFungibleId) {
+            for proposal in self.proposals.values_mut() {
+                if proposal.proposer == *old_id {
+                    proposal.proposer = new_id.clone();
+                }
+                if proposal.approvals.remove(old_id) {
+                    proposal.approvals.insert(new_id.clone());
+                }
+            }
+            for queued in self.timelock_queue.values_mut() {
+                if queued.queued_by == *old_id {
+                    queued.queued_by = new_id.clone();
+                }
+            }
+        }
+
+        fn update_approval_threshold(&mut self, threshold: u32) {
+            assert!(
+                threshold >= 1 && threshold <= self.admin_count,
+                "Approval threshold must be between 1 and the number of admins ({})",
+                self.admin_count
+            );
+            emit_event(
+                "multisig.set_approval_threshold",
+                metadata!(
+                    "old_threshold" => self.approval_threshold.to_string(),
+                    "new_threshold" => threshold.to_string(),
+                ),
+            );
+            self.approval_threshold = threshold;
+        }
+
+        /// Builds the component access rules. Methods tied to a role may only be called with a badge holding that
+        /// role, the remaining admin methods with any admin badge.
+        fn access_rules(&self) -> AccessRules {
+            let admin_resource = self.admin_auth_manager.resource_address();
+            let user_auth_resource = self.user_auth_manager.resource_address();
+            let require_user_or_admin = rule!(any_of(
+                resource(admin_resource),
+                resource(user_auth_resource)
+            ));
+            let mut rules = AccessRules::new()
+                .add_method_rule("transfer", require_user_or_admin.clone())
+                .add_method_rule("stealth_transfer", require_user_or_admin)
+                .default(rule!(resource(admin_resource)));
+
+            for method in VIEW_METHODS {
+                rules = rules.add_method_rule(*method, rule!(allow_all));
+            }
+
+            for role in AdminRole::ALL {
+                let rule = self.role_rule(role);
+                for method in role.methods() {
+                    rules = rules.add_method_rule(*method, rule.clone());
+                }
+            }
+            rules
+        }
+
+        fn update_access_rules(&self) {
+            self.component_badge.authorize_with(|| {
+                ComponentManager::current().set_access_rules(self.access_rules())
+            });
+        }
+
+        fn role_rule(&self, role: AdminRole) -> AccessRule {
+            let admin_resource = self.admin_auth_manager.resource_address();
+            let holders = self
+                .admin_roles
+                .get(&role)
+                .into_iter()
+                .flatten()
+                .map(|id| {
+                    RuleRequirement::NonFungibleAddress(NonFungibleAddress::new(
+                        admin_resource,
+                        id.clone(),
+                    ))
+                })
+                .collect::<Box<[_]>>();
+            if holders.is_empty() {
+                return rule!(deny_all);
+            }
+            AccessRule::Restricted(RestrictedAccessRule::Require(RequireRule::AnyOf(holders)))
+        }
+
+        fn assert_not_paused(&self) {
+            assert!(!self.is_paused, "Token is paused");
+        }
+
+        fn assert_not_denied(&self, component: Option<&ComponentAddress>) {
+            if let Some(component) = component {
+                assert!(
+                    !self.deny_list.contains_component(component),
+                    "Account {} is denied",
+                    component
+                );
+            }
+            let signer = CallerContext::transaction_signer_public_key();
+            assert!(
+                !self.deny_list.contains_public_key(&signer),
+                "Transactions signed by {} are denied",
+                signer
+            );
+        }
+
+        fn get_admin_badge_id(&self, proof: &Proof) -> NonFungibleId {
+            proof.assert_resource(self.admin_auth_manager.resource_address());
+            let badges = proof.get_non_fungibles();
+            assert_eq!(
+                badges.len(),
+                1,
+                "Proof must contain exactly one admin badge"
+            );
+            badges.into_iter().next().unwrap()
+        }
+
+        fn token_vault_manager(&self) -> ResourceManager {
+            self.token_vault.get_resource_manager()
+        }
+
+        fn wrapped_token(&self) -> &WrappedExchangeToken {
+            self.wrapped_token
+                .as_ref()
+                .expect("Wrapped token is not enabled")
+        }
     }
 }
+
+/// Read-only methods that anyone may call
+const VIEW_METHODS: &[&str] = &[
+    "get_config",
+    "is_paused",
+    "total_supply",
+    "reserve_balance",
+    "wrapped_token_resource",
+    "get_user",
+    "list_blacklisted",
+    "list_deny_list",
+    "export_deny_list",
+    "get_queued_actions",
+];
+
+fn fee_override_to_string(fee: Option<&config::FeeSpec>) -> String {
+    fee.map_or_else(|| "none".to_string(), |fee| fee.to_string())
+}
@@ -0,0 +1,168 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use crate::admin_roles::AdminRole;
+use crate::config::FeeSpec;
+use crate::fee_override_to_string;
+use crate::user_data::UserId;
+use core::fmt;
+use tari_template_lib::types::{Amount, NonFungibleId};
+
+pub type TimelockId = u64;
+
+/// An admin action that must wait out the timelock delay before it takes effect, once a delay is configured
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum TimelockedAction {
+    IncreaseSupply {
+        amount: Amount,
+    },
+    DecreaseSupply {
+        amount: Amount,
+    },
+    SetTransferFee {
+        fee: FeeSpec,
+    },
+    SetWrappedExchangeFee {
+        fee: FeeSpec,
+    },
+    SetRedemptionFee {
+        fee: FeeSpec,
+    },
+    SetTimelockDelay {
+        delay_epochs: u64,
+    },
+    SetUserTransferFeeOverride {
+        user_id: UserId,
+        fee: Option<FeeSpec>,
+    },
+    SetUserWrappedExchangeFeeOverride {
+        user_id: UserId,
+        fee: Option<FeeSpec>,
+    },
+    SetExchangeLimitWindow {
+        window_epochs: u64,
+    },
+    SetDefaultExchangeLimit {
+        limit: Amount,
+    },
+    SetDefaultRedemptionLimit {
+        limit: Amount,
+    },
+    SetMintAllowance {
+        admin_badge_id: NonFungibleId,
+        allowance: Amount,
+    },
+}
+
+impl TimelockedAction {
+    /// The role required to queue, execute or cancel the action
+    pub fn required_role(&self) -> AdminRole {
+        match self {
+            Self::IncreaseSupply { .. } => AdminRole::Minter,
+            Self::DecreaseSupply { .. } => AdminRole::Burner,
+            Self::SetTransferFee { .. }
+            | Self::SetWrappedExchangeFee { .. }
+            | Self::SetRedemptionFee { .. }
+            | Self::SetUserTransferFeeOverride { .. }
+            | Self::SetUserWrappedExchangeFeeOverride { .. } => AdminRole::FeeManager,
+            Self::SetExchangeLimitWindow { .. }
+            | Self::SetDefaultExchangeLimit { .. }
+            | Self::SetDefaultRedemptionLimit { .. } => AdminRole::Compliance,
+            Self::SetTimelockDelay { .. } | Self::SetMintAllowance { .. } => AdminRole::Super,
+        }
+    }
+
+    /// Supply changes need the approval threshold to be met, both when they are queued and when they are executed
+    pub fn is_supply_change(&self) -> bool {
+        matches!(
+            self,
+            Self::IncreaseSupply { .. } | Self::DecreaseSupply { .. }
+        )
+    }
+
+    pub fn validate(&self) {
+        match self {
+            Self::SetTransferFee { fee }
+            | Self::SetWrappedExchangeFee { fee }
+            | Self::SetRedemptionFee { fee } => fee.validate(),
+            Self::SetUserTransferFeeOverride { fee, .. }
+            | Self::SetUserWrappedExchangeFeeOverride { fee, .. } => {
+                if let Some(fee) = fee {
+                    fee.validate();
+                }
+            }
+            Self::SetExchangeLimitWindow { window_epochs } => assert!(
+                *window_epochs > 0,
+                "Exchange limit window must be at least one epoch"
+            ),
+            Self::SetDefaultExchangeLimit { limit } => {
+                assert!(limit.is_positive(), "Exchange limit must be positive")
+            }
+            Self::SetDefaultRedemptionLimit { limit } => {
+                assert!(limit.is_positive(), "Redemption limit must be positive")
+            }
+            Self::IncreaseSupply { .. }
+            | Self::DecreaseSupply { .. }
+            | Self::SetTimelockDelay { .. }
+            | Self::SetMintAllowance { .. } => {}
+        }
+    }
+}
+
+impl fmt::Display for TimelockedAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IncreaseSupply { amount } => write!(f, "increase_supply({})", amount),
+            Self::DecreaseSupply { amount } => write!(f, "decrease_supply({})", amount),
+            Self::SetTransferFee { fee } => write!(f, "set_transfer_fee({})", fee),
+            Self::SetWrappedExchangeFee { fee } => write!(f, "set_wrapped_exchange_fee({})", fee),
+            Self::SetRedemptionFee { fee } => write!(f, "set_redemption_fee({})", fee),
+            Self::SetTimelockDelay { delay_epochs } => {
+                write!(f, "set_timelock_delay({})", delay_epochs)
+            }
+            Self::SetUserTransferFeeOverride { user_id, fee } => write!(
+                f,
+                "set_user_transfer_fee_override({}, {})",
+                user_id,
+                fee_override_to_string(fee.as_ref())
+            ),
+            Self::SetUserWrappedExchangeFeeOverride { user_id, fee } => write!(
+                f,
+                "set_user_wrapped_exchange_fee_override({}, {})",
+                user_id,
+                fee_override_to_string(fee.as_ref())
+            ),
+            Self::SetExchangeLimitWindow { window_epochs } => {
+                write!(f, "set_exchange_limit_window({})", window_epochs)
+            }
+            Self::SetDefaultExchangeLimit { limit } => {
+                write!(f, "set_default_exchange_limit({})", limit)
+            }
+            Self::SetDefaultRedemptionLimit { limit } => {
+                write!(f, "set_default_redemption_limit({})", limit)
+            }
+            Self::SetMintAllowance {
+                admin_badge_id,
+                allowance,
+            } => write!(f, "set_mint_allowance({}, {})", admin_badge_id, allowance),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct QueuedAction {
+    pub action: TimelockedAction,
+    /// The admin badge that queued the action. Its mint allowance is used for `IncreaseSupply`.
+    pub queued_by: NonFungibleId,
+    /// The number of admin approvals the action was queued with
+    pub approvals: u32,
+    pub queued_at_epoch: u64,
+    /// The action can be executed from this epoch onwards, and cancelled before it
+    pub execute_at_epoch: u64,
+}
+
+impl QueuedAction {
+    pub fn is_executable(&self, current_epoch: u64) -> bool {
+        current_epoch >= self.execute_at_epoch
+    }
+}
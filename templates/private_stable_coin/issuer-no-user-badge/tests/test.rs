@@ -1,11 +1,24 @@
 use ootle_byte_type::ToByteType;
-use tari_template_lib::types::{ComponentAddress, Metadata, NonFungibleAddress, ResourceAddress};
+use std::collections::BTreeMap;
+use std::iter;
+use tari_template_lib::types::{
+    Amount, ComponentAddress, Metadata, NonFungibleAddress, NonFungibleId, ResourceAddress,
+};
 use tari_template_test_tooling::TemplateTest;
 use tari_template_test_tooling::crypto::{PublicKey, RistrettoPublicKey, RistrettoSecretKey};
+use tari_template_test_tooling::engine_types::virtual_substate::{
+    VirtualSubstate, VirtualSubstateId,
+};
 use tari_template_test_tooling::support::assert_error::assert_reject_reason;
+use tari_template_test_tooling::support::stealth::{
+    NO_INPUTS, generate_transfer_data_with_view_key, generate_value_proof_mask_knowledge,
+};
+use tari_template_test_tooling::template_lib_types::stealth::SpendCondition;
 use tari_template_test_tooling::transaction::args;
+use tari_template_test_tooling::wallet_crypto::MaskAndValue;
 
 const INITIAL_SUPPLY: u128 = 1_000_000_000_000_000u128;
+const MAX_SUPPLY: u128 = 2 * INITIAL_SUPPLY;
 
 #[test]
 fn it_increases_and_decreases_supply() {
@@ -24,7 +37,16 @@ fn it_increases_and_decreases_supply() {
         test.transaction()
             .create_proof(admin_account, admin_badge_resource)
             .put_last_instruction_output_on_workspace("proof")
-            .call_method(stable_coin_component, "increase_supply", args![123])
+            .call_method(
+                stable_coin_component,
+                "set_mint_allowance",
+                args![NonFungibleId::from_u64(0), 123],
+            )
+            .call_method(
+                stable_coin_component,
+                "increase_supply",
+                args![Workspace("proof"), 123],
+            )
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof.clone()],
@@ -64,6 +86,7 @@ fn it_allows_users_to_transact() {
         admin_key,
         admin_account,
         admin_badge_resource,
+        user_badge_resource,
         token_resource,
         ..
     } = setup();
@@ -84,22 +107,31 @@ fn it_allows_users_to_transact() {
                 args![123, alice_account],
             )
             .put_last_instruction_output_on_workspace("badge")
-            .call_method(stable_coin_component, "withdraw", args![1234])
-            .put_last_instruction_output_on_workspace("funds")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![1234, alice_account],
+            )
             // Deposit badge and funds into Alice's account
             .call_method(alice_account, "deposit", args![Workspace("badge")])
-            .call_method(alice_account, "deposit", args![Workspace("funds")])
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof.clone()],
     );
 
-    // Alice to Bob should fail (Bob is not allowed to transact)
+    // Alice sends to Bob through the issuer. Bob does not need a badge to receive, and the default fixed fee of 1 is
+    // kept by the issuer.
     test.execute_expect_success(
         test.transaction()
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
             .call_method(alice_account, "withdraw", args![token_resource, 456])
-            .put_last_instruction_output_on_workspace("funds")
-            .call_method(bob_account, "deposit", args![Workspace("funds")])
+            .put_last_instruction_output_on_workspace("tokens")
+            .call_method(
+                stable_coin_component,
+                "transfer",
+                args![Workspace("proof"), Workspace("tokens"), bob_account],
+            )
             .drop_all_proofs_in_workspace()
             .build_and_seal(&alice_key),
         vec![alice_proof.clone()],
@@ -109,7 +141,7 @@ fn it_allows_users_to_transact() {
         .read_only_state_store()
         .get_vaults_for_account(bob_account)
         .unwrap();
-    assert_eq!(vaults.get(&token_resource).unwrap().balance(), 456);
+    assert_eq!(vaults.get(&token_resource).unwrap().balance(), 455);
 }
 
 #[test]
@@ -121,6 +153,7 @@ fn it_allows_anyone_to_receive_tokens_without_badge() {
         admin_key,
         admin_account,
         admin_badge_resource,
+        user_badge_resource,
         token_resource,
         ..
     } = setup();
@@ -128,25 +161,41 @@ fn it_allows_anyone_to_receive_tokens_without_badge() {
     let (alice_account, alice_proof, alice_key) = test.create_empty_account();
     let (bob_account, _, _) = test.create_empty_account();
 
-    // Fund Alice directly (no user badge needed)
+    // Fund Bob directly (no user badge needed to receive) and give Alice a badge so she can send
     test.execute_expect_success(
         test.transaction()
             .create_proof(admin_account, admin_badge_resource)
             .put_last_instruction_output_on_workspace("proof")
-            .call_method(stable_coin_component, "withdraw", args![1000])
-            .put_last_instruction_output_on_workspace("funds")
-            .call_method(alice_account, "deposit", args![Workspace("funds")])
+            .call_method(stable_coin_component, "withdraw", args![100, bob_account])
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("badge")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![1000, alice_account],
+            )
+            .call_method(alice_account, "deposit", args![Workspace("badge")])
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof],
     );
 
-    // Alice sends to Bob - no user badge needed for either party
+    // Alice sends to Bob, who has no user badge
     test.execute_expect_success(
         test.transaction()
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
             .call_method(alice_account, "withdraw", args![token_resource, 456])
-            .put_last_instruction_output_on_workspace("funds")
-            .call_method(bob_account, "deposit", args![Workspace("funds")])
+            .put_last_instruction_output_on_workspace("tokens")
+            .call_method(
+                stable_coin_component,
+                "transfer",
+                args![Workspace("proof"), Workspace("tokens"), bob_account],
+            )
             .drop_all_proofs_in_workspace()
             .build_and_seal(&alice_key),
         vec![alice_proof],
@@ -156,11 +205,11 @@ fn it_allows_anyone_to_receive_tokens_without_badge() {
         .read_only_state_store()
         .get_vaults_for_account(bob_account)
         .unwrap();
-    assert_eq!(vaults.get(&token_resource).unwrap().balance(), 456);
+    assert_eq!(vaults.get(&token_resource).unwrap().balance(), 555);
 }
 
 #[test]
-fn it_creates_new_admin() {
+fn it_enforces_mint_allowance_and_max_supply() {
     let TestSetup {
         mut test,
         stable_coin_component,
@@ -168,40 +217,96 @@ fn it_creates_new_admin() {
         admin_key,
         admin_account,
         admin_badge_resource,
+        token_resource,
         ..
     } = setup();
 
-    let (new_admin_account, _, _) = test.create_empty_account();
-
-    test.execute_expect_success(
+    let increase_supply = |test: &TemplateTest, amount: u128| {
         test.transaction()
             .create_proof(admin_account, admin_badge_resource)
             .put_last_instruction_output_on_workspace("proof")
             .call_method(
                 stable_coin_component,
-                "create_new_admin",
-                args!["employee_42"],
+                "increase_supply",
+                args![Workspace("proof"), amount],
             )
-            .put_last_instruction_output_on_workspace("new_admin_badge")
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+    let set_mint_allowance = |test: &TemplateTest, allowance: u128| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
             .call_method(
-                new_admin_account,
-                "deposit",
-                args![Workspace("new_admin_badge")],
+                stable_coin_component,
+                "set_mint_allowance",
+                args![NonFungibleId::from_u64(0), allowance],
             )
             .drop_all_proofs_in_workspace()
-            .build_and_seal(&admin_key),
-        vec![admin_proof],
+            .build_and_seal(&admin_key)
+    };
+
+    // The founding admin of a fresh instance may mint up to the maximum supply
+    let result =
+        test.execute_expect_success(increase_supply(&test, 100), vec![admin_proof.clone()]);
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|event| event.topic() == "TariStableCoin.increase_supply")
+        .expect("increase_supply event not found");
+    assert_eq!(
+        event.get_payload("remaining_allowance"),
+        Some((MAX_SUPPLY - INITIAL_SUPPLY - 100).to_string().as_str())
     );
 
-    let vaults = test
+    // The maximum supply applies regardless of the allowance
+    test.execute_expect_success(
+        set_mint_allowance(&test, MAX_SUPPLY),
+        vec![admin_proof.clone()],
+    );
+    let reason = test.execute_expect_failure(
+        increase_supply(&test, MAX_SUPPLY - INITIAL_SUPPLY - 99),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(&reason, "Maximum supply exceeded");
+
+    // Each mint is deducted from the allowance and names the admin badge
+    test.execute_expect_success(set_mint_allowance(&test, 150), vec![admin_proof.clone()]);
+    let result =
+        test.execute_expect_success(increase_supply(&test, 100), vec![admin_proof.clone()]);
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|event| event.topic() == "TariStableCoin.increase_supply")
+        .expect("increase_supply event not found");
+    assert_eq!(
+        event.get_payload("admin_badge"),
+        Some(NonFungibleId::from_u64(0).to_string().as_str())
+    );
+    assert_eq!(event.get_payload("remaining_allowance"), Some("50"));
+
+    let reason = test.execute_expect_failure(increase_supply(&test, 51), vec![admin_proof]);
+    assert_reject_reason(&reason, "Mint allowance exceeded");
+
+    let resource = test
         .read_only_state_store()
-        .get_vaults_for_account(new_admin_account)
+        .get_resource(&token_resource)
         .unwrap();
-    assert_eq!(vaults.get(&admin_badge_resource).unwrap().balance(), 1);
+    assert_eq!(resource.total_supply().unwrap(), INITIAL_SUPPLY + 200);
 }
 
 #[test]
-fn it_recalls_tokens_from_user() {
+fn it_requires_multiple_admin_approvals_when_threshold_is_set() {
+    // Mirror of the template's admin operation type, used to encode the call arguments
+    #[derive(serde::Serialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    enum AdminOperation {
+        DecreaseSupply { amount: u64 },
+        Withdraw { amount: u64 },
+    }
+
     let TestSetup {
         mut test,
         stable_coin_component,
@@ -213,65 +318,199 @@ fn it_recalls_tokens_from_user() {
         ..
     } = setup();
 
+    let (admin2_account, admin2_proof, admin2_key) = test.create_empty_account();
     let (alice_account, _, _) = test.create_empty_account();
 
-    // Create user and fund Alice
+    // Setup: create a second admin and require two approvals for critical operations
     test.execute_expect_success(
         test.transaction()
             .create_proof(admin_account, admin_badge_resource)
             .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "create_new_admin", args!["E2"])
+            .put_last_instruction_output_on_workspace("admin2_badge")
+            .call_method(admin2_account, "deposit", args![Workspace("admin2_badge")])
             .call_method(
                 stable_coin_component,
                 "create_new_user",
                 args![1, alice_account],
             )
             .put_last_instruction_output_on_workspace("alice_badge")
-            .call_method(stable_coin_component, "withdraw", args![500])
-            .put_last_instruction_output_on_workspace("funds")
             .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
-            .call_method(alice_account, "deposit", args![Workspace("funds")])
+            .call_method(stable_coin_component, "set_approval_threshold", args![2u32])
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof.clone()],
     );
 
-    let alice_vaults = test
+    // A single admin can no longer withdraw tokens or fees
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "withdraw", args![100, alice_account])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(
+        &reason,
+        "This operation requires 2 admin approvals, submit it as a proposal",
+    );
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "withdraw_fees", args![0])
+            .put_last_instruction_output_on_workspace("fees")
+            .call_method(alice_account, "deposit", args![Workspace("fees")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(
+        &reason,
+        "This operation requires 2 admin approvals, submit it as a proposal",
+    );
+
+    let propose = |test: &TemplateTest, operation: AdminOperation, expires_at_epoch: u64| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "propose",
+                args![Workspace("proof"), operation, expires_at_epoch],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+    let admin2_call = |test: &TemplateTest, method: &str, proposal_id: u64| {
+        test.transaction()
+            .create_proof(admin2_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                method,
+                args![Workspace("proof"), proposal_id],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin2_key)
+    };
+
+    // Admin 1 proposes a withdrawal, which counts as their approval
+    test.execute_expect_success(
+        propose(&test, AdminOperation::Withdraw { amount: 100 }, 10),
+        vec![admin_proof.clone()],
+    );
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "approve_proposal",
+                args![Workspace("proof"), 0u64],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(
+        &reason,
+        "This admin badge has already approved the proposal",
+    );
+
+    // Approvers must hold the role required by the operation, and the second admin has no roles yet
+    let reason = test.execute_expect_failure(
+        admin2_call(&test, "approve_proposal", 0),
+        vec![admin2_proof.clone()],
+    );
+    assert_reject_reason(
+        &reason,
+        "The minter or super role is required to approve this proposal",
+    );
+    let admin2_badge_id = test
         .read_only_state_store()
-        .get_vaults_for_account(alice_account)
+        .get_vaults_for_account(admin2_account)
+        .unwrap()
+        .get(&admin_badge_resource)
+        .unwrap()
+        .get_non_fungible_ids()
+        .first()
+        .cloned()
         .unwrap();
-    assert_eq!(alice_vaults.get(&token_resource).unwrap().balance(), 500);
-
-    // Recall 200 tokens from Alice
     test.execute_expect_success(
         test.transaction()
             .create_proof(admin_account, admin_badge_resource)
             .put_last_instruction_output_on_workspace("proof")
             .call_method(
                 stable_coin_component,
-                "recall_revealed_tokens",
-                args![1u64, 200],
+                "grant_admin_role",
+                args![admin2_badge_id, "Minter"],
             )
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
-        vec![admin_proof],
+        vec![admin_proof.clone()],
     );
 
+    // The second approval executes the withdrawal
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin2_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "approve_proposal",
+                args![Workspace("proof"), 0u64],
+            )
+            .put_last_instruction_output_on_workspace("funds")
+            .call_method(alice_account, "deposit", args![Workspace("funds")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin2_key),
+        vec![admin2_proof.clone()],
+    );
     let alice_vaults = test
         .read_only_state_store()
         .get_vaults_for_account(alice_account)
         .unwrap();
-    assert_eq!(alice_vaults.get(&token_resource).unwrap().balance(), 300);
+    assert_eq!(alice_vaults.get(&token_resource).unwrap().balance(), 100);
 
-    // Total supply unchanged (tokens moved to component vault, not burned)
-    let resource = test
-        .read_only_state_store()
-        .get_resource(&token_resource)
-        .unwrap();
-    assert_eq!(resource.total_supply().unwrap(), INITIAL_SUPPLY);
+    // Executed proposals cannot be approved again
+    let reason = test.execute_expect_failure(
+        admin2_call(&test, "approve_proposal", 0),
+        vec![admin2_proof.clone()],
+    );
+    assert_reject_reason(&reason, "Proposal not found");
+
+    // Only the proposer can cancel a proposal before it expires
+    test.execute_expect_success(
+        propose(&test, AdminOperation::DecreaseSupply { amount: 1 }, 2),
+        vec![admin_proof.clone()],
+    );
+    let reason = test.execute_expect_failure(
+        admin2_call(&test, "cancel_proposal", 1),
+        vec![admin2_proof.clone()],
+    );
+    assert_reject_reason(
+        &reason,
+        "Only the proposer may cancel a proposal before it expires",
+    );
+
+    // Expired proposals cannot be approved but can be cancelled by any admin
+    test.set_virtual_substate(
+        VirtualSubstateId::CurrentEpoch,
+        VirtualSubstate::CurrentEpoch(2),
+    );
+    let reason = test.execute_expect_failure(
+        admin2_call(&test, "approve_proposal", 1),
+        vec![admin2_proof.clone()],
+    );
+    assert_reject_reason(&reason, "Proposal has expired");
+    test.execute_expect_success(admin2_call(&test, "cancel_proposal", 1), vec![admin2_proof]);
 }
 
 #[test]
-fn it_blacklists_and_removes_from_blacklist() {
+fn it_creates_new_admin() {
     let TestSetup {
         mut test,
         stable_coin_component,
@@ -279,84 +518,530 @@ fn it_blacklists_and_removes_from_blacklist() {
         admin_key,
         admin_account,
         admin_badge_resource,
-        user_badge_resource,
         ..
     } = setup();
 
-    let (alice_account, _, _) = test.create_empty_account();
+    let (new_admin_account, _, _) = test.create_empty_account();
 
-    // Create user for Alice
     test.execute_expect_success(
         test.transaction()
             .create_proof(admin_account, admin_badge_resource)
             .put_last_instruction_output_on_workspace("proof")
             .call_method(
                 stable_coin_component,
-                "create_new_user",
-                args![1, alice_account],
+                "create_new_admin",
+                args!["employee_42"],
+            )
+            .put_last_instruction_output_on_workspace("new_admin_badge")
+            .call_method(
+                new_admin_account,
+                "deposit",
+                args![Workspace("new_admin_badge")],
             )
-            .put_last_instruction_output_on_workspace("alice_badge")
-            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
-        vec![admin_proof.clone()],
+        vec![admin_proof],
     );
 
-    // Get Alice's user badge vault ID
-    let alice_account_state = test
+    let vaults = test
         .read_only_state_store()
-        .get_account(alice_account)
+        .get_vaults_for_account(new_admin_account)
         .unwrap();
-    let alice_badge_vault_id = alice_account_state
-        .get_vault_by_resource(&user_badge_resource)
-        .unwrap()
-        .vault_id();
+    assert_eq!(vaults.get(&admin_badge_resource).unwrap().balance(), 1);
+}
+
+#[test]
+fn it_restricts_admin_methods_by_role() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        ..
+    } = setup();
+
+    let (admin2_account, admin2_proof, admin2_key) = test.create_empty_account();
 
-    // Blacklist Alice
     test.execute_expect_success(
         test.transaction()
             .create_proof(admin_account, admin_badge_resource)
             .put_last_instruction_output_on_workspace("proof")
-            .call_method(
-                stable_coin_component,
-                "blacklist_user",
-                args![alice_badge_vault_id, 1u64],
-            )
+            .call_method(stable_coin_component, "create_new_admin", args!["E2"])
+            .put_last_instruction_output_on_workspace("admin2_badge")
+            .call_method(admin2_account, "deposit", args![Workspace("admin2_badge")])
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof.clone()],
     );
-
-    // Verify Alice's user badge has been recalled
-    let alice_vaults = test
+    let admin2_badge_id = test
         .read_only_state_store()
-        .get_vaults_for_account(alice_account)
+        .get_vaults_for_account(admin2_account)
+        .unwrap()
+        .get(&admin_badge_resource)
+        .unwrap()
+        .get_non_fungible_ids()
+        .first()
+        .cloned()
         .unwrap();
-    assert_eq!(alice_vaults.get(&user_badge_resource).unwrap().balance(), 0);
 
-    // Remove Alice from the blacklist and re-deposit her badge
-    test.execute_expect_success(
+    let admin2_pause = |test: &TemplateTest| {
+        test.transaction()
+            .create_proof(admin2_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "pause", args![Workspace("proof")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin2_key)
+    };
+    let update_role = |test: &TemplateTest, method: &str, badge_id: &NonFungibleId| {
         test.transaction()
             .create_proof(admin_account, admin_badge_resource)
             .put_last_instruction_output_on_workspace("proof")
-            .call_method(stable_coin_component, "remove_from_blacklist", args![1u64])
-            .put_last_instruction_output_on_workspace("alice_badge")
-            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .call_method(stable_coin_component, method, args![badge_id, "Pauser"])
             .drop_all_proofs_in_workspace()
-            .build_and_seal(&admin_key),
-        vec![admin_proof],
+            .build_and_seal(&admin_key)
+    };
+
+    // A new admin has no roles
+    let reason = test.execute_expect_failure(admin2_pause(&test), vec![admin2_proof.clone()]);
+    assert_reject_reason(&reason, "Access Denied");
+
+    // Only super admins can grant roles
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin2_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "grant_admin_role",
+                args![&admin2_badge_id, "Pauser"],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin2_key),
+        vec![admin2_proof.clone()],
     );
+    assert_reject_reason(&reason, "Access Denied");
 
-    // Verify Alice has her badge back
-    let alice_vaults = test
-        .read_only_state_store()
-        .get_vaults_for_account(alice_account)
+    let result = test.execute_expect_success(
+        update_role(&test, "grant_admin_role", &admin2_badge_id),
+        vec![admin_proof.clone()],
+    );
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.admin.role_granted")
         .unwrap();
-    assert_eq!(alice_vaults.get(&user_badge_resource).unwrap().balance(), 1);
+    assert_eq!(event.get_payload("role").unwrap(), "pauser");
+    assert_eq!(
+        event.get_payload("admin_badge").unwrap(),
+        admin2_badge_id.to_string()
+    );
+
+    test.execute_expect_success(admin2_pause(&test), vec![admin2_proof.clone()]);
+
+    test.execute_expect_success(
+        update_role(&test, "revoke_admin_role", &admin2_badge_id),
+        vec![admin_proof.clone()],
+    );
+    let reason = test.execute_expect_failure(admin2_pause(&test), vec![admin2_proof]);
+    assert_reject_reason(&reason, "Access Denied");
+
+    // The last super admin cannot lose the super role
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "revoke_admin_role",
+                args![NonFungibleId::from_u64(0), "Super"],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof],
+    );
+    assert_reject_reason(
+        &reason,
+        "Cannot revoke the super role from the last super admin",
+    );
 }
 
 #[test]
-fn it_sets_config_transfer_fee() {
+fn it_revokes_and_rotates_admin_badges() {
+    // Mirrors of the template's admin operation and timelock types, used to encode the call arguments and decode the
+    // queue
+    #[derive(serde::Serialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    enum AdminOperation {
+        Withdraw {
+            amount: u64,
+        },
+        RevokeAdmin {
+            admin_account: ComponentAddress,
+            admin_badge_id: NonFungibleId,
+        },
+        RotateAdmin {
+            admin_account: ComponentAddress,
+            admin_badge_id: NonFungibleId,
+        },
+    }
+    #[derive(serde::Serialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    enum TimelockedAction {
+        IncreaseSupply { amount: u64 },
+    }
+    #[derive(serde::Deserialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    struct QueuedAction {
+        queued_by: NonFungibleId,
+    }
+
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        ..
+    } = setup();
+
+    let (admin2_account, admin2_proof, admin2_key) = test.create_empty_account();
+
+    let admin_badge_id = |test: &TemplateTest, account: ComponentAddress| {
+        test.read_only_state_store()
+            .get_vaults_for_account(account)
+            .unwrap()
+            .get(&admin_badge_resource)
+            .unwrap()
+            .get_non_fungible_ids()
+            .first()
+            .cloned()
+            .unwrap()
+    };
+    let super_admin_call = |test: &TemplateTest, method: &str, badge_id: NonFungibleId| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                method,
+                args![admin2_account, badge_id],
+            )
+            .drop_all_proofs_in_workspace()
+    };
+    let admin2_pause = |test: &TemplateTest| {
+        test.transaction()
+            .create_proof(admin2_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "pause", args![Workspace("proof")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin2_key)
+    };
+
+    // The last remaining admin cannot be revoked
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "revoke_admin",
+                args![admin_account, NonFungibleId::from_u64(0)],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(&reason, "Cannot revoke the last remaining admin");
+
+    let admin2_call = |test: &TemplateTest, method: &str, args| {
+        test.transaction()
+            .create_proof(admin2_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, method, args)
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin2_key)
+    };
+    let admin_call = |test: &TemplateTest, method: &str, args| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, method, args)
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+
+    // Create a second admin with the pauser and minter roles and a mint allowance, and enable a 5 epoch timelock
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "create_new_admin", args!["E2"])
+            .put_last_instruction_output_on_workspace("admin2_badge")
+            .call_method(admin2_account, "deposit", args![Workspace("admin2_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    let old_badge_id = admin_badge_id(&test, admin2_account);
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "grant_admin_role",
+                args![&old_badge_id, "Pauser"],
+            )
+            .call_method(
+                stable_coin_component,
+                "grant_admin_role",
+                args![&old_badge_id, "Minter"],
+            )
+            .call_method(
+                stable_coin_component,
+                "set_mint_allowance",
+                args![&old_badge_id, 500],
+            )
+            .call_method(
+                stable_coin_component,
+                "set_config_timelock_delay",
+                args![5u64],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // The second admin queues a supply increase against their allowance
+    test.execute_expect_success(
+        admin2_call(
+            &test,
+            "queue_action",
+            args![
+                Workspace("proof"),
+                TimelockedAction::IncreaseSupply { amount: 100 }
+            ],
+        ),
+        vec![admin2_proof.clone()],
+    );
+
+    // Rotating replaces the badge, keeping its roles
+    test.execute_expect_success(
+        super_admin_call(&test, "rotate_admin", old_badge_id.clone())
+            .put_last_instruction_output_on_workspace("new_badge")
+            .call_method(admin2_account, "deposit", args![Workspace("new_badge")])
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    let new_badge_id = admin_badge_id(&test, admin2_account);
+    assert_ne!(new_badge_id, old_badge_id);
+
+    // The queued action and the mint allowance move to the new badge
+    let result = test.execute_expect_success(
+        test.transaction()
+            .call_method(stable_coin_component, "get_queued_actions", args![])
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    let queue = result.finalize.execution_results[0]
+        .decode::<BTreeMap<u64, QueuedAction>>()
+        .unwrap();
+    assert_eq!(queue[&0].queued_by, new_badge_id);
+    test.set_virtual_substate(
+        VirtualSubstateId::CurrentEpoch,
+        VirtualSubstate::CurrentEpoch(5),
+    );
+    let result = test.execute_expect_success(
+        admin2_call(&test, "execute_action", args![Workspace("proof"), 0u64]),
+        vec![admin2_proof.clone()],
+    );
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.increase_supply")
+        .unwrap();
+    assert_eq!(
+        event.get_payload("admin_badge").unwrap(),
+        new_badge_id.to_string()
+    );
+    assert_eq!(event.get_payload("remaining_allowance").unwrap(), "400");
+
+    // Queue another supply increase, which is still pending when the badge is revoked
+    test.execute_expect_success(
+        admin2_call(
+            &test,
+            "queue_action",
+            args![
+                Workspace("proof"),
+                TimelockedAction::IncreaseSupply { amount: 100 }
+            ],
+        ),
+        vec![admin2_proof.clone()],
+    );
+    test.execute_expect_success(admin2_pause(&test), vec![admin2_proof.clone()]);
+
+    // Add a third super admin and require two approvals, so rotations and revocations go through proposals
+    let (admin3_account, admin3_proof, admin3_key) = test.create_empty_account();
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "create_new_admin", args!["E3"])
+            .put_last_instruction_output_on_workspace("admin3_badge")
+            .call_method(admin3_account, "deposit", args![Workspace("admin3_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    let admin3_badge_id = admin_badge_id(&test, admin3_account);
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "grant_admin_role",
+                args![&admin3_badge_id, "Super"],
+            )
+            .call_method(stable_coin_component, "set_approval_threshold", args![2u32])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    let admin3_approve = |test: &TemplateTest, proposal_id: u64| {
+        test.transaction()
+            .create_proof(admin3_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "approve_proposal",
+                args![Workspace("proof"), proposal_id],
+            )
+    };
+
+    // The second admin's proposal is handed over to the rotated badge, which can then cancel it as the proposer
+    test.execute_expect_success(
+        admin2_call(
+            &test,
+            "propose",
+            args![
+                Workspace("proof"),
+                AdminOperation::Withdraw { amount: 1 },
+                100u64
+            ],
+        ),
+        vec![admin2_proof.clone()],
+    );
+    test.execute_expect_success(
+        admin_call(
+            &test,
+            "propose",
+            args![
+                Workspace("proof"),
+                AdminOperation::RotateAdmin {
+                    admin_account: admin2_account,
+                    admin_badge_id: new_badge_id.clone(),
+                },
+                100u64
+            ],
+        ),
+        vec![admin_proof.clone()],
+    );
+    test.execute_expect_success(
+        admin3_approve(&test, 1)
+            .put_last_instruction_output_on_workspace("new_badge")
+            .call_method(admin2_account, "deposit", args![Workspace("new_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin3_key),
+        vec![admin3_proof.clone()],
+    );
+    let rotated_badge_id = admin_badge_id(&test, admin2_account);
+    assert_ne!(rotated_badge_id, new_badge_id);
+    test.execute_expect_success(
+        admin2_call(&test, "cancel_proposal", args![Workspace("proof"), 0u64]),
+        vec![admin2_proof.clone()],
+    );
+
+    // Revoking burns the badge and its roles, and cancels its proposals and queued actions
+    test.execute_expect_success(
+        admin2_call(
+            &test,
+            "propose",
+            args![
+                Workspace("proof"),
+                AdminOperation::Withdraw { amount: 1 },
+                100u64
+            ],
+        ),
+        vec![admin2_proof],
+    );
+    test.execute_expect_success(
+        admin_call(
+            &test,
+            "propose",
+            args![
+                Workspace("proof"),
+                AdminOperation::RevokeAdmin {
+                    admin_account: admin2_account,
+                    admin_badge_id: rotated_badge_id,
+                },
+                100u64
+            ],
+        ),
+        vec![admin_proof],
+    );
+    let result = test.execute_expect_success(
+        admin3_approve(&test, 3)
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin3_key),
+        vec![admin3_proof],
+    );
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.multisig.cancelled")
+        .unwrap();
+    assert_eq!(event.get_payload("proposal_id").unwrap(), "2");
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.timelock.cancelled")
+        .unwrap();
+    assert_eq!(event.get_payload("timelock_id").unwrap(), "1");
+    let vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(admin2_account)
+        .unwrap();
+    assert_eq!(vaults.get(&admin_badge_resource).unwrap().balance(), 0);
+}
+
+#[test]
+fn it_timelocks_supply_and_fee_changes() {
+    // Mirrors of the template's timelock types, used to encode the call arguments and decode the queue
+    #[derive(serde::Serialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    enum FeeSpec {
+        Fixed(u64),
+    }
+    #[derive(serde::Serialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    enum TimelockedAction {
+        IncreaseSupply { amount: u64 },
+        SetTransferFee { fee: FeeSpec },
+    }
+    #[derive(serde::Deserialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    struct QueuedAction {
+        execute_at_epoch: u64,
+    }
+
     let TestSetup {
         mut test,
         stable_coin_component,
@@ -367,52 +1052,2074 @@ fn it_sets_config_transfer_fee() {
         ..
     } = setup();
 
-    // Set fixed transfer fee
+    let (_, alice_proof, alice_key) = test.create_empty_account();
+
+    let admin_call = |test: &TemplateTest, method: &str, args| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, method, args)
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+    let set_epoch = |test: &mut TemplateTest, epoch: u64| {
+        test.set_virtual_substate(
+            VirtualSubstateId::CurrentEpoch,
+            VirtualSubstate::CurrentEpoch(epoch),
+        );
+    };
+
+    // Enable a 5 epoch timelock
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "set_mint_allowance",
+                args![NonFungibleId::from_u64(0), 1000],
+            )
+            .call_method(
+                stable_coin_component,
+                "set_config_timelock_delay",
+                args![5u64],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    let reason = test.execute_expect_failure(
+        admin_call(&test, "increase_supply", args![Workspace("proof"), 1000]),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(
+        &reason,
+        "This operation is timelocked, queue it with queue_action",
+    );
+    let reason = test.execute_expect_failure(
+        admin_call(&test, "set_config_transfer_fee_fixed", args![7]),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(
+        &reason,
+        "This operation is timelocked, queue it with queue_action",
+    );
+
+    // Fee overrides, limits and mint allowances are timelocked too
+    for (method, args) in [
+        ("set_user_transfer_fee_override", args![1u64, None::<()>]),
+        (
+            "set_user_wrapped_exchange_fee_override",
+            args![1u64, None::<()>],
+        ),
+        ("set_config_exchange_limit_window", args![10u64]),
+        ("set_config_default_exchange_limit", args![1000]),
+        ("set_config_default_redemption_limit", args![1000]),
+        (
+            "set_mint_allowance",
+            args![NonFungibleId::from_u64(0), 2000],
+        ),
+    ] {
+        let reason =
+            test.execute_expect_failure(admin_call(&test, method, args), vec![admin_proof.clone()]);
+        assert_reject_reason(
+            &reason,
+            "This operation is timelocked, queue it with queue_action",
+        );
+    }
+
+    // Queue a supply increase and a fee change
+    let result = test.execute_expect_success(
+        admin_call(
+            &test,
+            "queue_action",
+            args![
+                Workspace("proof"),
+                TimelockedAction::IncreaseSupply { amount: 1000 }
+            ],
+        ),
+        vec![admin_proof.clone()],
+    );
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.timelock.queued")
+        .unwrap();
+    assert_eq!(event.get_payload("timelock_id").unwrap(), "0");
+    assert_eq!(event.get_payload("execute_at_epoch").unwrap(), "5");
+    test.execute_expect_success(
+        admin_call(
+            &test,
+            "queue_action",
+            args![
+                Workspace("proof"),
+                TimelockedAction::SetTransferFee {
+                    fee: FeeSpec::Fixed(7)
+                }
+            ],
+        ),
+        vec![admin_proof.clone()],
+    );
+
+    // Anyone can view the queue
+    let result = test.execute_expect_success(
+        test.transaction()
+            .call_method(stable_coin_component, "get_queued_actions", args![])
+            .build_and_seal(&alice_key),
+        vec![alice_proof.clone()],
+    );
+    let queue = result.finalize.execution_results[0]
+        .decode::<BTreeMap<u64, QueuedAction>>()
+        .unwrap();
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue[&1].execute_at_epoch, 5);
+
+    // Actions cannot be executed before the delay has passed, but can be cancelled
+    set_epoch(&mut test, 4);
+    let reason = test.execute_expect_failure(
+        admin_call(&test, "execute_action", args![Workspace("proof"), 0u64]),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(
+        &reason,
+        "Timelocked action cannot be executed before epoch 5",
+    );
+    test.execute_expect_success(
+        admin_call(&test, "cancel_action", args![Workspace("proof"), 1u64]),
+        vec![admin_proof.clone()],
+    );
+
+    // Once the delay has passed the action can be executed, but no longer cancelled
+    set_epoch(&mut test, 5);
+    let reason = test.execute_expect_failure(
+        admin_call(&test, "cancel_action", args![Workspace("proof"), 0u64]),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(&reason, "Timelocked action can no longer be cancelled");
+    let result = test.execute_expect_success(
+        admin_call(&test, "execute_action", args![Workspace("proof"), 0u64]),
+        vec![admin_proof],
+    );
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.increase_supply")
+        .unwrap();
+    assert_eq!(event.get_payload("amount").unwrap(), "1000");
+
+    let result = test.execute_expect_success(
+        test.transaction()
+            .call_method(stable_coin_component, "get_queued_actions", args![])
+            .build_and_seal(&alice_key),
+        vec![alice_proof],
+    );
+    let queue = result.finalize.execution_results[0]
+        .decode::<BTreeMap<u64, QueuedAction>>()
+        .unwrap();
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn it_rejects_queued_supply_changes_once_more_approvals_are_required() {
+    // Mirror of the template's timelock action type, used to encode the call arguments
+    #[derive(serde::Serialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    enum TimelockedAction {
+        IncreaseSupply { amount: u64 },
+    }
+
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        ..
+    } = setup();
+
+    let (admin2_account, _, _) = test.create_empty_account();
+
+    let admin_call = |test: &TemplateTest, method: &str, args| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, method, args)
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+
+    // Queue a supply increase while a single approval is enough
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "set_mint_allowance",
+                args![NonFungibleId::from_u64(0), 1000],
+            )
+            .call_method(
+                stable_coin_component,
+                "set_config_timelock_delay",
+                args![5u64],
+            )
+            .call_method(
+                stable_coin_component,
+                "queue_action",
+                args![
+                    Workspace("proof"),
+                    TimelockedAction::IncreaseSupply { amount: 1000 }
+                ],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Add a second admin and require both to approve supply changes
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "create_new_admin", args!["E2"])
+            .put_last_instruction_output_on_workspace("admin2_badge")
+            .call_method(admin2_account, "deposit", args![Workspace("admin2_badge")])
+            .call_method(stable_coin_component, "set_approval_threshold", args![2u32])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // The queued increase no longer meets the threshold once its delay has passed
+    test.set_virtual_substate(
+        VirtualSubstateId::CurrentEpoch,
+        VirtualSubstate::CurrentEpoch(5),
+    );
+    let reason = test.execute_expect_failure(
+        admin_call(&test, "execute_action", args![Workspace("proof"), 0u64]),
+        vec![admin_proof],
+    );
+    assert_reject_reason(
+        &reason,
+        "This action was queued with 1 admin approvals but 2 are now required, submit it as a proposal",
+    );
+}
+
+#[test]
+fn it_exposes_read_only_views() {
+    // Partial mirrors of the template's config and user data, used to decode the view results
+    #[derive(serde::Deserialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    struct StableCoinConfig {
+        default_exchange_limit: Amount,
+        timelock_delay_epochs: u64,
+    }
+    #[derive(serde::Deserialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    struct UserData {
+        user_id: u64,
+    }
+    #[derive(serde::Deserialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    struct UserMutableData {
+        is_blacklisted: bool,
+    }
+
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        ..
+    } = setup();
+
+    let (alice_account, _, _) = test.create_empty_account();
+    let (_, bob_proof, bob_key) = test.create_empty_account();
+
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "blacklist_user",
+                args![1u64, false, Vec::<()>::new()],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Anyone may call the views without a badge
+    let result = test.execute_expect_success(
+        test.transaction()
+            .call_method(stable_coin_component, "get_config", args![])
+            .call_method(stable_coin_component, "is_paused", args![])
+            .call_method(stable_coin_component, "total_supply", args![])
+            .call_method(stable_coin_component, "reserve_balance", args![])
+            .call_method(stable_coin_component, "wrapped_token_resource", args![])
+            .call_method(stable_coin_component, "get_user", args![1u64])
+            .call_method(stable_coin_component, "list_blacklisted", args![])
+            .build_and_seal(&bob_key),
+        vec![bob_proof],
+    );
+    let results = &result.finalize.execution_results;
+
+    let config = results[0].decode::<StableCoinConfig>().unwrap();
+    assert_eq!(config.default_exchange_limit, 1000);
+    assert_eq!(config.timelock_delay_epochs, 0);
+    assert!(!results[1].decode::<bool>().unwrap());
+    assert_eq!(results[2].decode::<Amount>().unwrap(), INITIAL_SUPPLY);
+    assert_eq!(results[3].decode::<Amount>().unwrap(), INITIAL_SUPPLY);
+    assert!(
+        results[4]
+            .decode::<Option<ResourceAddress>>()
+            .unwrap()
+            .is_some()
+    );
+    let (user_data, user_mutable_data) =
+        results[5].decode::<(UserData, UserMutableData)>().unwrap();
+    assert_eq!(user_data.user_id, 1);
+    assert!(user_mutable_data.is_blacklisted);
+    assert_eq!(results[6].decode::<Vec<u64>>().unwrap(), vec![1]);
+}
+
+#[test]
+fn it_pauses_and_resumes_token_movements() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        token_resource,
+        ..
+    } = setup();
+
+    // Withdraw some funds and pause in the same flow
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![1000, admin_account],
+            )
+            .call_method(stable_coin_component, "pause", args![Workspace("proof")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Token movements are refused while paused
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "increase_supply",
+                args![Workspace("proof"), 1000],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(&reason, "Token is paused");
+
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(admin_account, "withdraw", args![token_resource, 100])
+            .put_last_instruction_output_on_workspace("funds")
+            .call_method(stable_coin_component, "deposit", args![Workspace("funds")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(&reason, "Token is paused");
+
+    // Resume and withdraw again
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "unpause", args![Workspace("proof")])
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![1000, admin_account],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof],
+    );
+
+    let vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(admin_account)
+        .unwrap();
+    assert_eq!(vaults.get(&token_resource).unwrap().balance(), 2000);
+}
+
+#[test]
+fn it_freezes_and_unfreezes_user_accounts() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
+        token_resource,
+        ..
+    } = setup();
+
+    let (alice_account, alice_proof, alice_key) = test.create_empty_account();
+    let (bob_account, _, _) = test.create_empty_account();
+
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .call_method(stable_coin_component, "withdraw", args![500, alice_account])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    let admin_call = |test: &TemplateTest, method: &str| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, method, args![1u64])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+    let alice_to_bob = |test: &TemplateTest| {
+        test.transaction()
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(alice_account, "withdraw", args![token_resource, 100])
+            .put_last_instruction_output_on_workspace("tokens")
+            .call_method(
+                stable_coin_component,
+                "transfer",
+                args![Workspace("proof"), Workspace("tokens"), bob_account],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&alice_key)
+    };
+    let fund_alice = |test: &TemplateTest| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "withdraw", args![100, alice_account])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+
+    let stable_to_wrapped = |test: &TemplateTest| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("admin_proof")
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("user_proof")
+            .call_method(alice_account, "withdraw", args![token_resource, 100])
+            .put_last_instruction_output_on_workspace("stable_tokens")
+            .call_method(
+                stable_coin_component,
+                "exchange_stable_for_wrapped_tokens",
+                args![Workspace("user_proof"), Workspace("stable_tokens")],
+            )
+            .put_last_instruction_output_on_workspace("wrapped_tokens")
+            .call_method(alice_account, "deposit", args![Workspace("wrapped_tokens")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+    let wrapped_to_stable = |test: &TemplateTest, wrapped_resource: ResourceAddress| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("admin_proof")
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("user_proof")
+            .call_method(alice_account, "withdraw", args![wrapped_resource, 50])
+            .put_last_instruction_output_on_workspace("wrapped_tokens")
+            .call_method(
+                stable_coin_component,
+                "exchange_wrapped_for_stable_tokens",
+                args![Workspace("user_proof"), Workspace("wrapped_tokens")],
+            )
+            .put_last_instruction_output_on_workspace("stable_tokens")
+            .call_method(alice_account, "deposit", args![Workspace("stable_tokens")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+
+    // Give Alice some wrapped tokens to redeem later
+    test.execute_expect_success(
+        stable_to_wrapped(&test),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+    let wrapped_resource = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap()
+        .iter()
+        .find(|(addr, vault)| {
+            **addr != token_resource && **addr != user_badge_resource && vault.balance() > 0
+        })
+        .map(|(addr, _)| *addr)
+        .expect("Alice should have wrapped tokens");
+
+    let result =
+        test.execute_expect_success(admin_call(&test, "freeze_user"), vec![admin_proof.clone()]);
+    assert!(
+        result
+            .finalize
+            .events
+            .iter()
+            .any(|e| e.topic() == "TariStableCoin.freeze_user")
+    );
+
+    // Alice's revealed token vault is frozen, so she cannot withdraw tokens to send or exchange them, nor receive into
+    // it. Redeeming wrapped tokens is rejected by the issuer.
+    let reason = test.execute_expect_failure(alice_to_bob(&test), vec![alice_proof.clone()]);
+    assert_reject_reason(&reason, "is frozen for Withdrawals");
+    let reason = test.execute_expect_failure(
+        stable_to_wrapped(&test),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+    assert_reject_reason(&reason, "is frozen for Withdrawals");
+    let reason = test.execute_expect_failure(
+        wrapped_to_stable(&test, wrapped_resource),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+    assert_reject_reason(&reason, "User 0000000000000000001 is frozen");
+    let reason = test.execute_expect_failure(fund_alice(&test), vec![admin_proof.clone()]);
+    assert_reject_reason(&reason, "is frozen for Deposits");
+
+    // Unfreezing restores each of them
+    test.execute_expect_success(
+        admin_call(&test, "unfreeze_user"),
+        vec![admin_proof.clone()],
+    );
+    test.execute_expect_success(alice_to_bob(&test), vec![alice_proof.clone()]);
+    test.execute_expect_success(
+        stable_to_wrapped(&test),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+    test.execute_expect_success(
+        wrapped_to_stable(&test, wrapped_resource),
+        vec![admin_proof.clone(), alice_proof],
+    );
+    test.execute_expect_success(fund_alice(&test), vec![admin_proof.clone()]);
+
+    let reason = test.execute_expect_failure(admin_call(&test, "unfreeze_user"), vec![admin_proof]);
+    assert_reject_reason(&reason, "is not frozen");
+
+    let alice_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    assert_eq!(
+        alice_vaults.get(&token_resource).unwrap().balance(),
+        Amount::new(350)
+    );
+}
+
+#[test]
+fn it_seizes_and_reissues_user_funds() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        token_resource,
+        ..
+    } = setup();
+
+    let (alice_account, _, _) = test.create_empty_account();
+    let (bob_account, _, _) = test.create_empty_account();
+
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .call_method(stable_coin_component, "withdraw", args![500, alice_account])
+            .call_method(stable_coin_component, "freeze_user", args![1u64])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    let seize = |test: &TemplateTest, case_reference: &str| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "seize_and_reissue",
+                args![1u64, Vec::<()>::new(), case_reference],
+            )
+            .put_last_instruction_output_on_workspace("seized")
+            .call_method(bob_account, "deposit", args![Workspace("seized")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+
+    let reason = test.execute_expect_failure(seize(&test, ""), vec![admin_proof.clone()]);
+    assert_reject_reason(&reason, "A case reference is required");
+
+    // The frozen user's revealed balance is moved to the recovery account
+    let result = test.execute_expect_success(seize(&test, "CASE-42"), vec![admin_proof.clone()]);
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.seize_and_reissue")
+        .unwrap();
+    assert_eq!(event.get_payload("case_reference"), Some("CASE-42"));
+    assert_eq!(event.get_payload("revealed_amount"), Some("500"));
+    assert_eq!(event.get_payload("burned_amount"), Some("0"));
+
+    let alice_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    assert_eq!(alice_vaults.get(&token_resource).unwrap().balance(), 0);
+    let bob_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(bob_account)
+        .unwrap();
+    assert_eq!(bob_vaults.get(&token_resource).unwrap().balance(), 500);
+
+    let reason = test.execute_expect_failure(seize(&test, "CASE-42"), vec![admin_proof]);
+    assert_reject_reason(&reason, "has no funds to seize");
+}
+
+#[test]
+fn it_seizes_and_reissues_stealth_utxos() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        token_resource,
+        ..
+    } = setup();
+
+    let (alice_account, _, alice_key) = test.create_empty_account();
+    let (bob_account, _, _) = test.create_empty_account();
+    let alice_public_key = RistrettoPublicKey::from_secret_key(&alice_key);
+    let view_key = RistrettoPublicKey::from_secret_key(&admin_key);
+
+    // Alice holds 50 revealed tokens and a 100 token UTXO that only she can spend
+    let mint = generate_transfer_data_with_view_key(
+        NO_INPUTS,
+        100u64,
+        [(100, SpendCondition::Signed(alice_public_key.to_byte_type()))],
+        0u64,
+        &view_key,
+    );
+    let result = test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .call_method(stable_coin_component, "withdraw", args![50, alice_account])
+            .call_method(stable_coin_component, "withdraw", args![100, admin_account])
+            .call_method(admin_account, "withdraw", args![token_resource, 100])
+            .put_last_instruction_output_on_workspace("funds")
+            .stealth_transfer_with_input_bucket(token_resource, mint.statement, "funds")
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    let utxo_id = *result
+        .finalize
+        .result
+        .accept()
+        .unwrap()
+        .up_iter()
+        .find_map(|(id, _)| id.as_utxo_address())
+        .expect("The UTXO should have been created")
+        .id();
+    let value_proof = generate_value_proof_mask_knowledge(Amount::new(100), &mint.output_masks[0]);
+
+    let admin_call = |test: &TemplateTest, method: &str, args| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, method, args)
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+    let seize = |test: &TemplateTest| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "seize_and_reissue",
+                args![1u64, vec![(utxo_id, value_proof.clone())], "CASE-7"],
+            )
+            .put_last_instruction_output_on_workspace("seized")
+            .call_method(bob_account, "deposit", args![Workspace("seized")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+
+    test.execute_expect_success(
+        admin_call(&test, "freeze_user", args![1u64]),
+        vec![admin_proof.clone()],
+    );
+    test.execute_expect_success(
+        admin_call(&test, "freeze_utxos", args![vec![utxo_id]]),
+        vec![admin_proof.clone()],
+    );
+
+    // The revealed balance is recalled and the frozen UTXO burned, and both are reissued into the recovery account
+    let result = test.execute_expect_success(seize(&test), vec![admin_proof.clone()]);
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.seize.burn_utxo")
+        .unwrap();
+    assert_eq!(event.get_payload("case_reference"), Some("CASE-7"));
+    assert_eq!(
+        event.get_payload("utxo_id"),
+        Some(utxo_id.to_string().as_str())
+    );
+    assert_eq!(event.get_payload("amount"), Some("100"));
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.seize_and_reissue")
+        .unwrap();
+    assert_eq!(event.get_payload("case_reference"), Some("CASE-7"));
+    assert_eq!(event.get_payload("revealed_amount"), Some("50"));
+    assert_eq!(event.get_payload("burned_amount"), Some("100"));
+
+    let bob_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(bob_account)
+        .unwrap();
+    assert_eq!(bob_vaults.get(&token_resource).unwrap().balance(), 150);
+
+    // The UTXO has been burnt, so it cannot be seized a second time
+    let reason = test.execute_expect_failure(seize(&test), vec![admin_proof]);
+    assert_reject_reason(&reason, "already burnt");
+}
+
+#[test]
+fn it_blacklists_user_and_recalls_revealed_balance() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
+        token_resource,
+        ..
+    } = setup();
+
+    let (alice_account, _, _) = test.create_empty_account();
+
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(stable_coin_component, "withdraw", args![500, alice_account])
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    let blacklist_alice = |test: &TemplateTest| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "blacklist_user",
+                args![1u64, true, Vec::<()>::new()],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+
+    // The badge vault is found through the user's account, and the revealed balance is recalled with it
+    let result = test.execute_expect_success(blacklist_alice(&test), vec![admin_proof.clone()]);
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.blacklist_user")
+        .unwrap();
+    assert_eq!(event.get_payload("revealed_amount"), Some("500"));
+
+    let alice_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    assert_eq!(alice_vaults.get(&user_badge_resource).unwrap().balance(), 0);
+    assert_eq!(alice_vaults.get(&token_resource).unwrap().balance(), 0);
+
+    let reason = test.execute_expect_failure(blacklist_alice(&test), vec![admin_proof]);
+    assert_reject_reason(&reason, "is already blacklisted");
+}
+
+#[test]
+fn it_recalls_tokens_from_user() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        token_resource,
+        ..
+    } = setup();
+
+    let (alice_account, _, _) = test.create_empty_account();
+
+    // Create user and fund Alice
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(stable_coin_component, "withdraw", args![500, alice_account])
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    let alice_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    assert_eq!(alice_vaults.get(&token_resource).unwrap().balance(), 500);
+
+    // Recall 200 tokens from Alice
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "recall_revealed_tokens",
+                args![1u64, 200],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof],
+    );
+
+    let alice_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    assert_eq!(alice_vaults.get(&token_resource).unwrap().balance(), 300);
+
+    // Total supply unchanged (tokens moved to component vault, not burned)
+    let resource = test
+        .read_only_state_store()
+        .get_resource(&token_resource)
+        .unwrap();
+    assert_eq!(resource.total_supply().unwrap(), INITIAL_SUPPLY);
+}
+
+#[test]
+fn it_applies_batch_compliance_operations() {
+    // Mirrors of the template's batch result types
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    enum BatchOutcome {
+        Applied,
+        Skipped { reason: String },
+    }
+    #[derive(serde::Deserialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    struct BatchEntryResult {
+        user_id: u64,
+        outcome: BatchOutcome,
+    }
+
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        token_resource,
+        ..
+    } = setup();
+
+    let (alice_account, _, _) = test.create_empty_account();
+    let (bob_account, _, _) = test.create_empty_account();
+
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![2, bob_account],
+            )
+            .put_last_instruction_output_on_workspace("bob_badge")
+            .call_method(stable_coin_component, "withdraw", args![300, alice_account])
+            .call_method(stable_coin_component, "withdraw", args![50, bob_account])
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .call_method(bob_account, "deposit", args![Workspace("bob_badge")])
+            // Carol's badge is held by Bob, so her account does not hold it
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![3, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("carol_badge")
+            .call_method(bob_account, "deposit", args![Workspace("carol_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    let admin_call = |test: &TemplateTest, method: &str, args| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, method, args)
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+
+    // Bob does not have enough revealed funds and user 9 does not exist, so only Alice's recall is applied
+    let result = test.execute_expect_success(
+        admin_call(
+            &test,
+            "batch_recall_revealed_tokens",
+            args![vec![(1u64, 100u64), (2u64, 100u64), (9u64, 100u64)]],
+        ),
+        vec![admin_proof.clone()],
+    );
+    let results = result.finalize.execution_results[2]
+        .decode::<Vec<BatchEntryResult>>()
+        .unwrap();
+    assert_eq!(results[0].user_id, 1);
+    assert_eq!(results[0].outcome, BatchOutcome::Applied);
+    assert_eq!(results[1].user_id, 2);
+    assert!(matches!(results[1].outcome, BatchOutcome::Skipped { .. }));
+    assert_eq!(
+        results[2].outcome,
+        BatchOutcome::Skipped {
+            reason: "User 0000000000000000009 does not exist".to_string()
+        }
+    );
+    let skipped_events = result
+        .finalize
+        .events
+        .iter()
+        .filter(|e| e.topic() == "TariStableCoin.batch.skipped")
+        .count();
+    assert_eq!(skipped_events, 2);
+    let alice_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    assert_eq!(alice_vaults.get(&token_resource).unwrap().balance(), 200);
+
+    // Non-positive limits and unknown users are skipped
+    let result = test.execute_expect_success(
+        admin_call(
+            &test,
+            "batch_set_user_exchange_limit",
+            args![vec![(1u64, 500u64), (2u64, 0u64), (9u64, 500u64)]],
+        ),
+        vec![admin_proof.clone()],
+    );
+    let results = result.finalize.execution_results[2]
+        .decode::<Vec<BatchEntryResult>>()
+        .unwrap();
+    assert_eq!(results[0].outcome, BatchOutcome::Applied);
+    assert_eq!(
+        results[1].outcome,
+        BatchOutcome::Skipped {
+            reason: "Exchange limit must be positive".to_string()
+        }
+    );
+    assert_eq!(
+        results[2].outcome,
+        BatchOutcome::Skipped {
+            reason: "User 0000000000000000009 does not exist".to_string()
+        }
+    );
+
+    // A user listed twice is only blacklisted once, and unknown users and users whose account does not hold their
+    // badge are skipped
+    let result = test.execute_expect_success(
+        admin_call(
+            &test,
+            "batch_blacklist_users",
+            args![vec![1u64, 2u64, 1u64, 9u64, 3u64], false],
+        ),
+        vec![admin_proof.clone()],
+    );
+    let results = result.finalize.execution_results[2]
+        .decode::<Vec<BatchEntryResult>>()
+        .unwrap();
+    assert_eq!(results.len(), 5);
+    assert_eq!(results[1].outcome, BatchOutcome::Applied);
+    assert!(matches!(results[2].outcome, BatchOutcome::Skipped { .. }));
+    assert_eq!(
+        results[3].outcome,
+        BatchOutcome::Skipped {
+            reason: "User 0000000000000000009 does not exist".to_string()
+        }
+    );
+    assert_eq!(
+        results[4].outcome,
+        BatchOutcome::Skipped {
+            reason: "The user's account does not hold their user badge".to_string()
+        }
+    );
+    let blacklist_events = result
+        .finalize
+        .events
+        .iter()
+        .filter(|e| e.topic() == "TariStableCoin.blacklist_user")
+        .count();
+    assert_eq!(blacklist_events, 2);
+    let skipped_events = result
+        .finalize
+        .events
+        .iter()
+        .filter(|e| e.topic() == "TariStableCoin.batch.skipped")
+        .count();
+    assert_eq!(skipped_events, 3);
+
+    let result = test.execute_expect_success(
+        test.transaction()
+            .call_method(stable_coin_component, "list_blacklisted", args![])
+            .build_and_seal(&admin_key),
+        vec![admin_proof],
+    );
+    let mut blacklisted = result.finalize.execution_results[0]
+        .decode::<Vec<u64>>()
+        .unwrap();
+    blacklisted.sort();
+    assert_eq!(blacklisted, vec![1, 2]);
+}
+
+#[test]
+fn it_denies_deny_listed_accounts_and_signers() {
+    // Partial mirror of the template's deny list entry, used to decode the listing
+    #[derive(serde::Deserialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    struct DenyListEntry {
+        id: u64,
+        component: Option<ComponentAddress>,
+        reason_code: u32,
+    }
+
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
+        token_resource,
+    } = setup();
+
+    let (alice_account, alice_proof, alice_key) = test.create_empty_account();
+    let (bob_account, _, _) = test.create_empty_account();
+    let admin_public_key = RistrettoPublicKey::from_secret_key(&admin_key).to_byte_type();
+
+    let admin_call = |test: &TemplateTest, method: &str, args| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, method, args)
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+    let create_alice = |test: &TemplateTest| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+
+    // A denied account cannot be issued a user badge
+    let result = test.execute_expect_success(
+        admin_call(
+            &test,
+            "add_to_deny_list",
+            args![vec![alice_account], Vec::<()>::new(), 7u32],
+        ),
+        vec![admin_proof.clone()],
+    );
+    assert!(
+        result
+            .finalize
+            .events
+            .iter()
+            .any(|e| e.topic() == "TariStableCoin.deny_list.added")
+    );
+    let reason = test.execute_expect_failure(create_alice(&test), vec![admin_proof.clone()]);
+    assert_reject_reason(&reason, "is denied");
+
+    let result = test.execute_expect_success(
+        test.transaction()
+            .call_method(
+                stable_coin_component,
+                "list_deny_list",
+                args![None::<u64>, 10u32],
+            )
+            .call_method(stable_coin_component, "export_deny_list", args![])
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+    let results = &result.finalize.execution_results;
+    let page = results[0].decode::<Vec<DenyListEntry>>().unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].id, 0);
+    assert_eq!(page[0].component, Some(alice_account));
+    assert_eq!(page[0].reason_code, 7);
+    assert_eq!(results[1].decode::<Vec<DenyListEntry>>().unwrap().len(), 1);
+
+    // Transactions signed by a denied key are rejected too
+    test.execute_expect_success(
+        admin_call(
+            &test,
+            "remove_from_deny_list",
+            args![vec![alice_account], Vec::<()>::new()],
+        ),
+        vec![admin_proof.clone()],
+    );
+    test.execute_expect_success(
+        admin_call(
+            &test,
+            "add_to_deny_list",
+            args![Vec::<()>::new(), vec![admin_public_key], 7u32],
+        ),
+        vec![admin_proof.clone()],
+    );
+    let reason = test.execute_expect_failure(create_alice(&test), vec![admin_proof.clone()]);
+    assert_reject_reason(&reason, "are denied");
+
+    test.execute_expect_success(
+        admin_call(
+            &test,
+            "remove_from_deny_list",
+            args![Vec::<()>::new(), vec![admin_public_key]],
+        ),
+        vec![admin_proof.clone()],
+    );
+    test.execute_expect_success(create_alice(&test), vec![admin_proof.clone()]);
+
+    // Tokens cannot be withdrawn from the issuer or transferred by a user into a denied account
+    test.execute_expect_success(
+        admin_call(
+            &test,
+            "add_to_deny_list",
+            args![vec![bob_account], Vec::<()>::new(), 7u32],
+        ),
+        vec![admin_proof.clone()],
+    );
+    let reason = test.execute_expect_failure(
+        admin_call(&test, "withdraw", args![100, bob_account]),
+        vec![admin_proof.clone()],
+    );
+    assert_reject_reason(&reason, "is denied");
+    test.execute_expect_success(
+        admin_call(&test, "withdraw", args![100, alice_account]),
+        vec![admin_proof],
+    );
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(alice_account, "withdraw", args![token_resource, 50])
+            .put_last_instruction_output_on_workspace("tokens")
+            .call_method(
+                stable_coin_component,
+                "transfer",
+                args![Workspace("proof"), Workspace("tokens"), bob_account],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&alice_key),
+        vec![alice_proof],
+    );
+    assert_reject_reason(&reason, "is denied");
+}
+
+#[test]
+fn it_rejects_blacklisted_badges_still_held_by_the_user() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
+        token_resource,
+    } = setup();
+    // The issuer always recalls a badge when it blacklists a user, so the flag is set directly to cover a badge that
+    // was not recalled
+    let badge_editor = test.compile_new_template(
+        "TestBadgeEditor",
+        "../test_badge_editor",
+        &[],
+        iter::empty::<(&str, &str)>(),
+    );
+
+    let (alice_account, alice_proof, alice_key) = test.create_empty_account();
+    let (bob_account, _, _) = test.create_empty_account();
+
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![1000, alice_account],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Give Alice some wrapped tokens to redeem later
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("admin_proof")
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("user_proof")
+            .call_method(alice_account, "withdraw", args![token_resource, 100])
+            .put_last_instruction_output_on_workspace("stable_tokens")
+            .call_method(
+                stable_coin_component,
+                "exchange_stable_for_wrapped_tokens",
+                args![Workspace("user_proof"), Workspace("stable_tokens")],
+            )
+            .put_last_instruction_output_on_workspace("wrapped_tokens")
+            .call_method(alice_account, "deposit", args![Workspace("wrapped_tokens")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+    let wrapped_resource = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap()
+        .iter()
+        .find(|(addr, vault)| {
+            **addr != token_resource && **addr != user_badge_resource && vault.balance() > 0
+        })
+        .map(|(addr, _)| *addr)
+        .expect("Alice should have wrapped tokens");
+
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_function(
+                badge_editor,
+                "set_flag",
+                args![
+                    user_badge_resource,
+                    NonFungibleId::from_u64(1),
+                    "is_blacklisted",
+                    true
+                ],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("admin_proof")
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("user_proof")
+            .call_method(alice_account, "withdraw", args![token_resource, 100])
+            .put_last_instruction_output_on_workspace("stable_tokens")
+            .call_method(
+                stable_coin_component,
+                "exchange_stable_for_wrapped_tokens",
+                args![Workspace("user_proof"), Workspace("stable_tokens")],
+            )
+            .put_last_instruction_output_on_workspace("wrapped_tokens")
+            .call_method(alice_account, "deposit", args![Workspace("wrapped_tokens")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+    assert_reject_reason(&reason, "User 0000000000000000001 is blacklisted");
+
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("admin_proof")
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("user_proof")
+            .call_method(alice_account, "withdraw", args![wrapped_resource, 50])
+            .put_last_instruction_output_on_workspace("wrapped_tokens")
+            .call_method(
+                stable_coin_component,
+                "exchange_wrapped_for_stable_tokens",
+                args![Workspace("user_proof"), Workspace("wrapped_tokens")],
+            )
+            .put_last_instruction_output_on_workspace("stable_tokens")
+            .call_method(alice_account, "deposit", args![Workspace("stable_tokens")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof, alice_proof.clone()],
+    );
+    assert_reject_reason(&reason, "User 0000000000000000001 is blacklisted");
+
+    // Alice cannot send either
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(alice_account, "withdraw", args![token_resource, 100])
+            .put_last_instruction_output_on_workspace("tokens")
+            .call_method(
+                stable_coin_component,
+                "transfer",
+                args![Workspace("proof"), Workspace("tokens"), bob_account],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&alice_key),
+        vec![alice_proof],
+    );
+    assert_reject_reason(&reason, "User 0000000000000000001 is blacklisted");
+}
+
+#[test]
+fn it_blacklists_and_removes_from_blacklist() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
+        ..
+    } = setup();
+
+    let (alice_account, _, _) = test.create_empty_account();
+
+    // Create user for Alice
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Blacklist Alice
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "blacklist_user",
+                args![1u64, false, Vec::<()>::new()],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Verify Alice's user badge has been recalled
+    let alice_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    assert_eq!(alice_vaults.get(&user_badge_resource).unwrap().balance(), 0);
+
+    // Remove Alice from the blacklist and re-deposit her badge
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "remove_from_blacklist", args![1u64])
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof],
+    );
+
+    // Verify Alice has her badge back
+    let alice_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    assert_eq!(alice_vaults.get(&user_badge_resource).unwrap().balance(), 1);
+}
+
+#[test]
+fn it_sets_config_transfer_fee() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        ..
+    } = setup();
+
+    // Set fixed transfer fee
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "set_config_transfer_fee_fixed",
+                args![10],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Set percentage transfer fee
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "set_config_transfer_fee_percentage",
+                args![5u8],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Set basis point transfer fee: 0.25% but at least 1 and at most 50
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "set_config_transfer_fee_basis_points",
+                args![25u16, "HalfEven", Some(1), Some(50)],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Setting basis points > 10000 should fail
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "set_config_transfer_fee_basis_points",
+                args![10_001u16, "Up", None::<u64>, None::<u64>],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    assert_reject_reason(&reason, "Basis point fee must be between 0 and 10000");
+
+    // Setting percentage > 100 should fail
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "set_config_transfer_fee_percentage",
+                args![101u8],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof],
+    );
+
+    assert_reject_reason(&reason, "Percentage fee must be between 0 and 100");
+}
+
+#[test]
+fn it_charges_transfer_fee() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
+        token_resource,
+    } = setup();
+
+    let (alice_account, alice_proof, alice_key) = test.create_empty_account();
+    let (bob_account, _, _) = test.create_empty_account();
+
+    // Setup: create Alice's user badge, fund her and set a 5% transfer fee
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![1000, alice_account],
+            )
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .call_method(
+                stable_coin_component,
+                "set_config_transfer_fee_percentage",
+                args![5u8],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Alice transfers 200 to Bob through the issuer
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(alice_account, "withdraw", args![token_resource, 200])
+            .put_last_instruction_output_on_workspace("tokens")
+            .call_method(
+                stable_coin_component,
+                "transfer",
+                args![Workspace("proof"), Workspace("tokens"), bob_account],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&alice_key),
+        vec![alice_proof],
+    );
+
+    let vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(bob_account)
+        .unwrap();
+    assert_eq!(vaults.get(&token_resource).unwrap().balance(), 190);
+
+    // Admin withdraws the collected fees
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(stable_coin_component, "withdraw_fees", args![10])
+            .put_last_instruction_output_on_workspace("fees")
+            .call_method(admin_account, "deposit", args![Workspace("fees")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof],
+    );
+
+    let vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(admin_account)
+        .unwrap();
+    assert_eq!(vaults.get(&token_resource).unwrap().balance(), 10);
+}
+
+#[test]
+fn it_charges_transfer_fee_on_stealth_transfers() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
+        token_resource,
+    } = setup();
+
+    let (alice_account, alice_proof, alice_key) = test.create_empty_account();
+    let (bob_account, _, _) = test.create_empty_account();
+    let alice_public_key = RistrettoPublicKey::from_secret_key(&alice_key);
+    let view_key = RistrettoPublicKey::from_secret_key(&admin_key);
+
+    // Issue a 100 token UTXO that only Alice can spend
+    let mint = generate_transfer_data_with_view_key(
+        NO_INPUTS,
+        100u64,
+        [(100, SpendCondition::Signed(alice_public_key.to_byte_type()))],
+        0u64,
+        &view_key,
+    );
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .call_method(stable_coin_component, "withdraw", args![100, admin_account])
+            .call_method(admin_account, "withdraw", args![token_resource, 100])
+            .put_last_instruction_output_on_workspace("funds")
+            .stealth_transfer_with_input_bucket(token_resource, mint.statement, "funds")
+            .call_method(stable_coin_component, "withdraw", args![10, alice_account])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof],
+    );
+    let utxo = MaskAndValue::new(100, mint.output_masks[0].clone());
+
+    // Alice keeps 60 private and reveals 40 to Bob. The fee is calculated on the revealed 40, so the default fixed fee
+    // of 1 applies, and she pays it from her revealed balance.
+    let spend = generate_transfer_data_with_view_key([utxo], 0u64, [60u64], 40u64, &view_key);
+    let stealth_transfer = |test: &TemplateTest, fee: u64| {
+        test.transaction()
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(alice_account, "withdraw", args![token_resource, fee])
+            .put_last_instruction_output_on_workspace("fee")
+            .call_method(
+                stable_coin_component,
+                "stealth_transfer",
+                args![
+                    Workspace("proof"),
+                    spend.statement.clone(),
+                    None::<()>,
+                    Workspace("fee")
+                ],
+            )
+            .put_last_instruction_output_on_workspace("revealed")
+            .call_method(bob_account, "deposit", args![Workspace("revealed")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&alice_key)
+    };
+
+    let reason = test.execute_expect_failure(stealth_transfer(&test, 2), vec![alice_proof.clone()]);
+    assert_reject_reason(&reason, "The fee bucket must contain the transfer fee of 1");
+
+    let result = test.execute_expect_success(stealth_transfer(&test, 1), vec![alice_proof]);
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.stealth_transfer")
+        .expect("stealth_transfer event not found");
+    assert_eq!(event.get_payload("user_id").unwrap(), "0000000000000000001");
+    assert_eq!(event.get_payload("stealth_outputs").unwrap(), "1");
+    assert_eq!(event.get_payload("revealed_amount").unwrap(), "40");
+    assert_eq!(event.get_payload("fee").unwrap(), "1");
+
+    let vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(bob_account)
+        .unwrap();
+    assert_eq!(vaults.get(&token_resource).unwrap().balance(), 40);
+    let vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    assert_eq!(vaults.get(&token_resource).unwrap().balance(), 9);
+
+    let indexed = test
+        .read_only_state_store()
+        .inspect_component(stable_coin_component)
+        .unwrap();
+    let fee_vault = indexed
+        .get_value("$.fee_vault")
+        .unwrap()
+        .expect("fee_vault not found");
+    let vault = test.read_only_state_store().get_vault(&fee_vault).unwrap();
+    assert_eq!(vault.balance(), 1);
+}
+
+#[test]
+fn it_sets_config_wrapped_exchange_fee_and_default_exchange_limit() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
+        token_resource,
+    } = setup();
+
+    let (alice_account, alice_proof, _alice_key) = test.create_empty_account();
+
+    // Set a fixed wrapped exchange fee and lower the default exchange limit before creating Alice
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "set_config_wrapped_exchange_fee_fixed",
+                args![5],
+            )
+            .call_method(
+                stable_coin_component,
+                "set_config_default_exchange_limit",
+                args![50],
+            )
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![1000, alice_account],
+            )
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Exchanging more than the new default limit fails
+    let reason = test.execute_expect_failure(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("admin_proof")
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("user_proof")
+            .call_method(alice_account, "withdraw", args![token_resource, 100])
+            .put_last_instruction_output_on_workspace("stable_tokens")
+            .call_method(
+                stable_coin_component,
+                "exchange_stable_for_wrapped_tokens",
+                args![Workspace("user_proof"), Workspace("stable_tokens")],
+            )
+            .put_last_instruction_output_on_workspace("wrapped_tokens")
+            .call_method(alice_account, "deposit", args![Workspace("wrapped_tokens")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+    assert_reject_reason(&reason, "Exchange limit exceeded");
+
+    // Exchanging within the limit charges the fixed fee
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("admin_proof")
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("user_proof")
+            .call_method(alice_account, "withdraw", args![token_resource, 50])
+            .put_last_instruction_output_on_workspace("stable_tokens")
+            .call_method(
+                stable_coin_component,
+                "exchange_stable_for_wrapped_tokens",
+                args![Workspace("user_proof"), Workspace("stable_tokens")],
+            )
+            .put_last_instruction_output_on_workspace("wrapped_tokens")
+            .call_method(alice_account, "deposit", args![Workspace("wrapped_tokens")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof, alice_proof],
+    );
+
+    let alice_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    let wrapped_resource = alice_vaults
+        .iter()
+        .find(|(addr, vault)| {
+            **addr != token_resource && **addr != user_badge_resource && vault.balance() > 0
+        })
+        .map(|(addr, _)| *addr)
+        .expect("Alice should have wrapped tokens");
+
+    assert_eq!(alice_vaults.get(&wrapped_resource).unwrap().balance(), 45);
+}
+
+#[test]
+fn it_applies_fee_tiers_and_user_fee_overrides() {
+    // Mirrors of the template's fee types, used to encode the call arguments
+    #[derive(serde::Serialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    enum FeeSpec {
+        Fixed(u64),
+        Percentage(u8),
+    }
+
+    #[derive(serde::Serialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    struct FeeTier {
+        up_to: Option<u64>,
+        fee: FeeSpec,
+    }
+
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
+        token_resource,
+    } = setup();
+
+    let (alice_account, alice_proof, alice_key) = test.create_empty_account();
+    let (bob_account, _, _) = test.create_empty_account();
+
+    // Setup: create users, fund Alice and charge 5% under 1000 and a flat fee of 2 above that
     test.execute_expect_success(
         test.transaction()
             .create_proof(admin_account, admin_badge_resource)
             .put_last_instruction_output_on_workspace("proof")
             .call_method(
                 stable_coin_component,
-                "set_config_transfer_fee_fixed",
-                args![10],
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![2, bob_account],
+            )
+            .put_last_instruction_output_on_workspace("bob_badge")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![2000, alice_account],
+            )
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .call_method(bob_account, "deposit", args![Workspace("bob_badge")])
+            .call_method(
+                stable_coin_component,
+                "set_config_transfer_fee_tiers",
+                args![vec![
+                    FeeTier {
+                        up_to: Some(1000),
+                        fee: FeeSpec::Percentage(5),
+                    },
+                    FeeTier {
+                        up_to: None,
+                        fee: FeeSpec::Fixed(2),
+                    },
+                ]],
             )
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof.clone()],
     );
 
-    // Set percentage transfer fee
+    let transfer_to_bob = |test: &mut TemplateTest, amount: u64| {
+        test.execute_expect_success(
+            test.transaction()
+                .create_proof(alice_account, user_badge_resource)
+                .put_last_instruction_output_on_workspace("proof")
+                .call_method(alice_account, "withdraw", args![token_resource, amount])
+                .put_last_instruction_output_on_workspace("tokens")
+                .call_method(
+                    stable_coin_component,
+                    "transfer",
+                    args![Workspace("proof"), Workspace("tokens"), bob_account],
+                )
+                .drop_all_proofs_in_workspace()
+                .build_and_seal(&alice_key),
+            vec![alice_proof.clone()],
+        );
+    };
+    let bob_balance = |test: &TemplateTest| {
+        test.read_only_state_store()
+            .get_vaults_for_account(bob_account)
+            .unwrap()
+            .get(&token_resource)
+            .unwrap()
+            .balance()
+    };
+
+    // 5% of 200
+    transfer_to_bob(&mut test, 200);
+    assert_eq!(bob_balance(&test), 190);
+
+    // Flat fee above 1000
+    transfer_to_bob(&mut test, 1000);
+    assert_eq!(bob_balance(&test), 190 + 998);
+
+    // Alice's override waives the transfer fee
     test.execute_expect_success(
         test.transaction()
             .create_proof(admin_account, admin_badge_resource)
             .put_last_instruction_output_on_workspace("proof")
             .call_method(
                 stable_coin_component,
-                "set_config_transfer_fee_percentage",
-                args![5u8],
+                "set_user_transfer_fee_override",
+                args![1, Some(FeeSpec::Fixed(0))],
             )
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof.clone()],
     );
 
-    // Setting percentage > 100 should fail
+    transfer_to_bob(&mut test, 100);
+    assert_eq!(bob_balance(&test), 190 + 998 + 100);
+
+    // Tier bounds must be increasing
     let reason = test.execute_expect_failure(
         test.transaction()
             .create_proof(admin_account, admin_badge_resource)
             .put_last_instruction_output_on_workspace("proof")
             .call_method(
                 stable_coin_component,
-                "set_config_transfer_fee_percentage",
-                args![101u8],
+                "set_config_transfer_fee_tiers",
+                args![vec![
+                    FeeTier {
+                        up_to: Some(1000),
+                        fee: FeeSpec::Percentage(5),
+                    },
+                    FeeTier {
+                        up_to: Some(500),
+                        fee: FeeSpec::Percentage(2),
+                    },
+                    FeeTier {
+                        up_to: None,
+                        fee: FeeSpec::Fixed(2),
+                    },
+                ]],
             )
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof],
     );
-
-    assert_reject_reason(&reason, "Percentage fee must be between 0 and 100");
+    assert_reject_reason(&reason, "Fee tier bounds must be strictly increasing");
 }
 
 #[test]
@@ -420,15 +3127,44 @@ fn it_prevents_non_admin_from_calling_admin_methods() {
     let TestSetup {
         mut test,
         stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
         ..
     } = setup();
 
-    let (_alice_account, alice_proof, alice_key) = test.create_empty_account();
+    let (alice_account, alice_proof, alice_key) = test.create_empty_account();
+
+    // Create Alice as a user so that she has a (non-admin) badge to present
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof],
+    );
 
     // Alice (non-admin) tries to increase supply - should be denied
     let reason = test.execute_expect_failure(
         test.transaction()
-            .call_method(stable_coin_component, "increase_supply", args![100])
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "increase_supply",
+                args![Workspace("proof"), 100],
+            )
+            .drop_all_proofs_in_workspace()
             .build_and_seal(&alice_key),
         vec![alice_proof.clone()],
     );
@@ -477,10 +3213,12 @@ fn it_exchanges_stable_for_wrapped_tokens() {
                 args![1, alice_account],
             )
             .put_last_instruction_output_on_workspace("alice_badge")
-            .call_method(stable_coin_component, "withdraw", args![1000])
-            .put_last_instruction_output_on_workspace("funds")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![1000, alice_account],
+            )
             .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
-            .call_method(alice_account, "deposit", args![Workspace("funds")])
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof.clone()],
@@ -553,10 +3291,12 @@ fn it_exchanges_wrapped_for_stable_tokens() {
                 args![1, alice_account],
             )
             .put_last_instruction_output_on_workspace("alice_badge")
-            .call_method(stable_coin_component, "withdraw", args![1000])
-            .put_last_instruction_output_on_workspace("funds")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![1000, alice_account],
+            )
             .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
-            .call_method(alice_account, "deposit", args![Workspace("funds")])
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof.clone()],
@@ -659,10 +3399,12 @@ fn it_enforces_exchange_limit() {
                 args![1, alice_account],
             )
             .put_last_instruction_output_on_workspace("alice_badge")
-            .call_method(stable_coin_component, "withdraw", args![2000])
-            .put_last_instruction_output_on_workspace("funds")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![2000, alice_account],
+            )
             .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
-            .call_method(alice_account, "deposit", args![Workspace("funds")])
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof.clone()],
@@ -692,6 +3434,246 @@ fn it_enforces_exchange_limit() {
     assert_reject_reason(&reason, "Exchange limit exceeded");
 }
 
+#[test]
+fn it_enforces_redemption_limit_and_fee() {
+    // Mirror of the template's fee type, used to encode the call arguments
+    #[derive(serde::Serialize)]
+    #[serde(crate = "tari_template_lib::template_macro_deps::serde")]
+    enum FeeSpec {
+        Fixed(u64),
+    }
+
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
+        token_resource,
+    } = setup();
+
+    let (alice_account, alice_proof, _alice_key) = test.create_empty_account();
+
+    // Setup: create user, fund Alice, charge 2 per redemption and allow Alice to redeem at most 300
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![1000, alice_account],
+            )
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .call_method(
+                stable_coin_component,
+                "set_config_redemption_fee",
+                args![FeeSpec::Fixed(2)],
+            )
+            .call_method(
+                stable_coin_component,
+                "set_user_redemption_limit",
+                args![1, 300],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    // Exchange 500 stable for 495 wrapped tokens
+    let result = test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("admin_proof")
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("user_proof")
+            .call_method(alice_account, "withdraw", args![token_resource, 500])
+            .put_last_instruction_output_on_workspace("stable_tokens")
+            .call_method(
+                stable_coin_component,
+                "exchange_stable_for_wrapped_tokens",
+                args![Workspace("user_proof"), Workspace("stable_tokens")],
+            )
+            .put_last_instruction_output_on_workspace("wrapped_tokens")
+            .call_method(alice_account, "deposit", args![Workspace("wrapped_tokens")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+
+    let wrapped_resource = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap()
+        .iter()
+        .find(|(addr, vault)| {
+            **addr != token_resource && **addr != user_badge_resource && vault.balance() > 0
+        })
+        .map(|(addr, _)| *addr)
+        .expect("Alice should have wrapped tokens");
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.exchange_stable_for_wrapped_tokens")
+        .unwrap();
+    assert_eq!(event.get_payload("gross_amount").unwrap(), "500");
+    assert_eq!(event.get_payload("net_amount").unwrap(), "495");
+    assert_eq!(event.get_payload("fee").unwrap(), "5");
+
+    let redeem = |test: &TemplateTest, amount: u64| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("admin_proof")
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("user_proof")
+            .call_method(alice_account, "withdraw", args![wrapped_resource, amount])
+            .put_last_instruction_output_on_workspace("wrapped_tokens")
+            .call_method(
+                stable_coin_component,
+                "exchange_wrapped_for_stable_tokens",
+                args![Workspace("user_proof"), Workspace("wrapped_tokens")],
+            )
+            .put_last_instruction_output_on_workspace("stable_tokens")
+            .call_method(alice_account, "deposit", args![Workspace("stable_tokens")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+
+    // Redeeming more than the limit fails
+    let reason = test.execute_expect_failure(
+        redeem(&test, 301),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+    assert_reject_reason(&reason, "Redemption limit exceeded");
+
+    // Redeeming up to the limit succeeds and the fee is deducted
+    let result = test.execute_expect_success(
+        redeem(&test, 300),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+    let event = result
+        .finalize
+        .events
+        .iter()
+        .find(|e| e.topic() == "TariStableCoin.exchange_wrapped_for_stable_tokens")
+        .unwrap();
+    assert_eq!(event.get_payload("gross_amount").unwrap(), "300");
+    assert_eq!(event.get_payload("net_amount").unwrap(), "298");
+    assert_eq!(event.get_payload("fee").unwrap(), "2");
+
+    let alice_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    assert_eq!(alice_vaults.get(&token_resource).unwrap().balance(), 798);
+    assert_eq!(alice_vaults.get(&wrapped_resource).unwrap().balance(), 195);
+
+    // The limit is used up for the rest of the window
+    let reason = test.execute_expect_failure(redeem(&test, 1), vec![admin_proof, alice_proof]);
+    assert_reject_reason(&reason, "Redemption limit exceeded");
+}
+
+#[test]
+fn it_resets_exchange_limit_after_window() {
+    let TestSetup {
+        mut test,
+        stable_coin_component,
+        admin_proof,
+        admin_key,
+        admin_account,
+        admin_badge_resource,
+        user_badge_resource,
+        token_resource,
+    } = setup();
+
+    let (alice_account, alice_proof, _alice_key) = test.create_empty_account();
+
+    // Setup: create user, fund Alice and use a two epoch exchange window
+    test.execute_expect_success(
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("proof")
+            .call_method(
+                stable_coin_component,
+                "create_new_user",
+                args![1, alice_account],
+            )
+            .put_last_instruction_output_on_workspace("alice_badge")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![3000, alice_account],
+            )
+            .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
+            .call_method(
+                stable_coin_component,
+                "set_config_exchange_limit_window",
+                args![2u64],
+            )
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key),
+        vec![admin_proof.clone()],
+    );
+
+    let exchange = |test: &TemplateTest, amount: u64| {
+        test.transaction()
+            .create_proof(admin_account, admin_badge_resource)
+            .put_last_instruction_output_on_workspace("admin_proof")
+            .create_proof(alice_account, user_badge_resource)
+            .put_last_instruction_output_on_workspace("user_proof")
+            .call_method(alice_account, "withdraw", args![token_resource, amount])
+            .put_last_instruction_output_on_workspace("stable_tokens")
+            .call_method(
+                stable_coin_component,
+                "exchange_stable_for_wrapped_tokens",
+                args![Workspace("user_proof"), Workspace("stable_tokens")],
+            )
+            .put_last_instruction_output_on_workspace("wrapped_tokens")
+            .call_method(alice_account, "deposit", args![Workspace("wrapped_tokens")])
+            .drop_all_proofs_in_workspace()
+            .build_and_seal(&admin_key)
+    };
+
+    // Use the full default limit (1000) in the first window
+    test.execute_expect_success(
+        exchange(&test, 1000),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+
+    // The window has not rolled over yet
+    test.set_virtual_substate(
+        VirtualSubstateId::CurrentEpoch,
+        VirtualSubstate::CurrentEpoch(1),
+    );
+    let reason = test.execute_expect_failure(
+        exchange(&test, 100),
+        vec![admin_proof.clone(), alice_proof.clone()],
+    );
+    assert_reject_reason(&reason, "Exchange limit exceeded");
+
+    // A new window starts and the allowance is reset
+    test.set_virtual_substate(
+        VirtualSubstateId::CurrentEpoch,
+        VirtualSubstate::CurrentEpoch(2),
+    );
+    test.execute_expect_success(exchange(&test, 1000), vec![admin_proof, alice_proof]);
+
+    let alice_vaults = test
+        .read_only_state_store()
+        .get_vaults_for_account(alice_account)
+        .unwrap();
+    assert_eq!(alice_vaults.get(&token_resource).unwrap().balance(), 1000);
+}
+
 #[test]
 fn it_sets_user_exchange_limit() {
     let TestSetup {
@@ -718,10 +3700,12 @@ fn it_sets_user_exchange_limit() {
                 args![1, alice_account],
             )
             .put_last_instruction_output_on_workspace("alice_badge")
-            .call_method(stable_coin_component, "withdraw", args![5000])
-            .put_last_instruction_output_on_workspace("funds")
+            .call_method(
+                stable_coin_component,
+                "withdraw",
+                args![5000, alice_account],
+            )
             .call_method(alice_account, "deposit", args![Workspace("alice_badge")])
-            .call_method(alice_account, "deposit", args![Workspace("funds")])
             .drop_all_proofs_in_workspace()
             .build_and_seal(&admin_key),
         vec![admin_proof.clone()],
@@ -803,11 +3787,13 @@ fn setup() -> TestSetup {
                 args![
                     Workspace("stable_coin_addr"),
                     INITIAL_SUPPLY,
+                    MAX_SUPPLY,
                     "SC4U",
                     metadata,
                     8,
                     view_key,
-                    true
+                    true,
+                    None::<()>
                 ],
             )
             .put_last_instruction_output_on_workspace("admin_badge")
@@ -1,14 +1,22 @@
 // Copyright 2024 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
+use alloc::vec::Vec;
 use core::fmt;
 use tari_template_lib::types::Amount;
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct StableCoinConfig {
     pub transfer_fee: FeeSpec,
     pub wrapped_exchange_fee: FeeSpec,
     pub default_exchange_limit: Amount,
+    /// The fee charged when exchanging wrapped tokens back for stable tokens
+    pub redemption_fee: FeeSpec,
+    pub default_redemption_limit: Amount,
+    /// The number of epochs after which a user's exchange and redemption allowances are reset
+    pub exchange_limit_window_epochs: u64,
+    /// The number of epochs a timelocked action must wait before it can be executed. Zero disables the timelock.
+    pub timelock_delay_epochs: u64,
 }
 
 impl Default for StableCoinConfig {
@@ -17,14 +25,41 @@ impl Default for StableCoinConfig {
             wrapped_exchange_fee: FeeSpec::Percentage(1),
             transfer_fee: FeeSpec::Fixed(1u64.into()),
             default_exchange_limit: 1000u64.into(),
+            redemption_fee: FeeSpec::Fixed(Amount::ZERO),
+            default_redemption_limit: 1000u64.into(),
+            exchange_limit_window_epochs: 1,
+            timelock_delay_epochs: 0,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+impl StableCoinConfig {
+    pub fn validate(&self) {
+        self.transfer_fee.validate();
+        self.wrapped_exchange_fee.validate();
+        self.redemption_fee.validate();
+        assert!(
+            self.default_exchange_limit.is_positive(),
+            "Exchange limit must be positive"
+        );
+        assert!(
+            self.default_redemption_limit.is_positive(),
+            "Redemption limit must be positive"
+        );
+        assert!(
+            self.exchange_limit_window_epochs > 0,
+            "Exchange limit window must be at least one epoch"
+        );
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum FeeSpec {
     Fixed(Amount),
     Percentage(u8),
+    BasisPoints(BasisPointFee),
+    /// A fee schedule where the tier is selected by the amount being charged
+    Tiered(Vec<FeeTier>),
 }
 
 impl FeeSpec {
@@ -32,6 +67,25 @@ impl FeeSpec {
         match self {
             Self::Fixed(fee) => *fee,
             Self::Percentage(percentage) => perc_rounded(amount, *percentage),
+            Self::BasisPoints(fee) => fee.calculate_fee(amount),
+            Self::Tiered(tiers) => tiers
+                .iter()
+                .find(|tier| tier.applies_to(amount))
+                .expect("Fee tiers must cover all amounts")
+                .fee
+                .calculate_fee(amount),
+        }
+    }
+
+    pub fn validate(&self) {
+        match self {
+            Self::Fixed(_) => {}
+            Self::Percentage(percentage) => assert!(
+                *percentage <= 100,
+                "Percentage fee must be between 0 and 100"
+            ),
+            Self::BasisPoints(fee) => fee.validate(),
+            Self::Tiered(tiers) => validate_tiers(tiers),
         }
     }
 }
@@ -41,10 +95,123 @@ impl fmt::Display for FeeSpec {
         match self {
             Self::Fixed(fee) => write!(f, "{}", fee),
             Self::Percentage(perc) => write!(f, "{}%", perc),
+            Self::BasisPoints(fee) => write!(f, "{}", fee),
+            Self::Tiered(tiers) => {
+                write!(f, "[")?;
+                for (i, tier) in tiers.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    match tier.up_to {
+                        Some(up_to) => write!(f, "<{}: {}", up_to, tier.fee)?,
+                        None => write!(f, "rest: {}", tier.fee)?,
+                    }
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// A band in a tiered fee schedule. The fee applies to amounts below `up_to`, or to any remaining amount if `up_to`
+/// is `None`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FeeTier {
+    pub up_to: Option<Amount>,
+    pub fee: FeeSpec,
+}
+
+impl FeeTier {
+    fn applies_to(&self, amount: Amount) -> bool {
+        self.up_to.is_none_or(|up_to| amount < up_to)
+    }
+}
+
+fn validate_tiers(tiers: &[FeeTier]) {
+    let Some((last, rest)) = tiers.split_last() else {
+        panic!("Fee tiers must not be empty");
+    };
+    assert!(
+        last.up_to.is_none(),
+        "The last fee tier must not have an upper bound"
+    );
+
+    let mut prev_up_to = None;
+    for tier in rest {
+        let up_to = tier.up_to.expect("Only the last fee tier may be unbounded");
+        if let Some(prev_up_to) = prev_up_to {
+            assert!(
+                up_to > prev_up_to,
+                "Fee tier bounds must be strictly increasing"
+            );
+        }
+        prev_up_to = Some(up_to);
+    }
+
+    for tier in tiers {
+        assert!(
+            !matches!(tier.fee, FeeSpec::Tiered(_)),
+            "Fee tiers cannot be nested"
+        );
+        tier.fee.validate();
+    }
+}
+
+/// The number of basis points in 100%
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// A fee expressed in basis points (1 bps = 0.01%) of the amount, rounded with the given mode and then clamped to the
+/// optional minimum and maximum fee.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct BasisPointFee {
+    pub bps: u16,
+    pub rounding: RoundingMode,
+    pub min_fee: Option<Amount>,
+    pub max_fee: Option<Amount>,
+}
+
+impl BasisPointFee {
+    pub fn calculate_fee(&self, amount: Amount) -> Amount {
+        let fee = bps_rounded(amount, self.bps, self.rounding);
+        let fee = self.min_fee.map_or(fee, |min_fee| fee.max(min_fee));
+        self.max_fee.map_or(fee, |max_fee| fee.min(max_fee))
+    }
+
+    pub fn validate(&self) {
+        assert!(
+            u128::from(self.bps) <= BPS_DENOMINATOR,
+            "Basis point fee must be between 0 and 10000"
+        );
+        if let (Some(min_fee), Some(max_fee)) = (self.min_fee, self.max_fee) {
+            assert!(
+                min_fee <= max_fee,
+                "Minimum fee must not be greater than the maximum fee"
+            );
         }
     }
 }
 
+impl fmt::Display for BasisPointFee {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}bps ({:?})", self.bps, self.rounding)?;
+        if let Some(min_fee) = self.min_fee {
+            write!(f, " min {}", min_fee)?;
+        }
+        if let Some(max_fee) = self.max_fee {
+            write!(f, " max {}", max_fee)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RoundingMode {
+    Up,
+    Down,
+    /// Round to nearest, ties to the even value (banker's rounding)
+    HalfEven,
+}
+
 fn perc_rounded<A: Into<Amount>>(v: A, percentage: u8) -> Amount {
     let v = v.into();
     let p = Amount::from(percentage);
@@ -62,6 +229,31 @@ fn perc_rounded<A: Into<Amount>>(v: A, percentage: u8) -> Amount {
     }
 }
 
+fn bps_rounded<A: Into<Amount>>(v: A, bps: u16, rounding: RoundingMode) -> Amount {
+    let v = v.into().to_u128();
+    let numerator = v
+        .checked_mul(u128::from(bps))
+        .expect("Fee calculation overflowed");
+    let quotient = numerator / BPS_DENOMINATOR;
+    let remainder = numerator % BPS_DENOMINATOR;
+
+    let round_up = match rounding {
+        RoundingMode::Up => remainder > 0,
+        RoundingMode::Down => false,
+        RoundingMode::HalfEven => {
+            let twice_remainder = remainder * 2;
+            twice_remainder > BPS_DENOMINATOR
+                || (twice_remainder == BPS_DENOMINATOR && quotient % 2 == 1)
+        }
+    };
+
+    if round_up {
+        Amount::new(quotient + 1)
+    } else {
+        Amount::new(quotient)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,4 +268,92 @@ mod tests {
         assert_eq!(perc_rounded(120u64, 10), 12);
         assert_eq!(perc_rounded(1234560000000u64, 11), 135801600000u64);
     }
+
+    #[test]
+    fn test_bps_round() {
+        use RoundingMode::*;
+
+        assert_eq!(bps_rounded(0u64, 25, Up), 0);
+        assert_eq!(bps_rounded(10_000u64, 0, Up), 0);
+        assert_eq!(bps_rounded(10_000u64, 25, Down), 25);
+        assert_eq!(bps_rounded(10_000u64, 10_000, Down), 10_000);
+
+        // 1234 * 5bps = 0.617
+        assert_eq!(bps_rounded(1234u64, 5, Up), 1);
+        assert_eq!(bps_rounded(1234u64, 5, Down), 0);
+        assert_eq!(bps_rounded(1234u64, 5, HalfEven), 1);
+
+        // 10_001 * 1bps = 1.0001
+        assert_eq!(bps_rounded(10_001u64, 1, Up), 2);
+        assert_eq!(bps_rounded(10_001u64, 1, Down), 1);
+        assert_eq!(bps_rounded(10_001u64, 1, HalfEven), 1);
+
+        // Ties round to the even value
+        assert_eq!(bps_rounded(5_000u64, 1, HalfEven), 0);
+        assert_eq!(bps_rounded(15_000u64, 1, HalfEven), 2);
+        assert_eq!(bps_rounded(25_000u64, 1, HalfEven), 2);
+        assert_eq!(bps_rounded(25_000u64, 1, Up), 3);
+        assert_eq!(bps_rounded(25_000u64, 1, Down), 2);
+    }
+
+    #[test]
+    fn test_bps_fee_caps() {
+        // 0.1% but at least 1 and at most 50
+        let fee = BasisPointFee {
+            bps: 10,
+            rounding: RoundingMode::HalfEven,
+            min_fee: Some(1u64.into()),
+            max_fee: Some(50u64.into()),
+        };
+
+        assert_eq!(fee.calculate_fee(100u64.into()), 1);
+        assert_eq!(fee.calculate_fee(10_000u64.into()), 10);
+        assert_eq!(fee.calculate_fee(25_000u64.into()), 25);
+        assert_eq!(fee.calculate_fee(1_000_000u64.into()), 50);
+
+        let uncapped = BasisPointFee {
+            min_fee: None,
+            max_fee: None,
+            ..fee
+        };
+        assert_eq!(uncapped.calculate_fee(100u64.into()), 0);
+        assert_eq!(uncapped.calculate_fee(1_000_000u64.into()), 1000);
+    }
+
+    #[test]
+    fn test_tiered_fee() {
+        // 0.5% under 10k, 0.2% up to 1M and a flat fee above that
+        let fee = FeeSpec::Tiered(alloc::vec![
+            FeeTier {
+                up_to: Some(10_000u64.into()),
+                fee: FeeSpec::BasisPoints(BasisPointFee {
+                    bps: 50,
+                    rounding: RoundingMode::HalfEven,
+                    min_fee: None,
+                    max_fee: None,
+                }),
+            },
+            FeeTier {
+                up_to: Some(1_000_000u64.into()),
+                fee: FeeSpec::BasisPoints(BasisPointFee {
+                    bps: 20,
+                    rounding: RoundingMode::HalfEven,
+                    min_fee: None,
+                    max_fee: None,
+                }),
+            },
+            FeeTier {
+                up_to: None,
+                fee: FeeSpec::Fixed(1500u64.into()),
+            },
+        ]);
+        fee.validate();
+
+        assert_eq!(fee.calculate_fee(1_000u64.into()), 5);
+        assert_eq!(fee.calculate_fee(9_999u64.into()), 50);
+        assert_eq!(fee.calculate_fee(10_000u64.into()), 20);
+        assert_eq!(fee.calculate_fee(999_999u64.into()), 2000);
+        assert_eq!(fee.calculate_fee(1_000_000u64.into()), 1500);
+        assert_eq!(fee.calculate_fee(50_000_000u64.into()), 1500);
+    }
 }
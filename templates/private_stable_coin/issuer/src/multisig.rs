@@ -0,0 +1,139 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use crate::admin_roles::AdminRole;
+use crate::user_data::UserId;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use tari_template_lib::types::crypto::StealthValueProof;
+use tari_template_lib::types::{Amount, ComponentAddress, NonFungibleId, UtxoId};
+
+pub type ProposalId = u64;
+
+/// A critical operation that requires approval from multiple admins once the approval threshold is above one
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum AdminOperation {
+    IncreaseSupply {
+        amount: Amount,
+    },
+    DecreaseSupply {
+        amount: Amount,
+    },
+    Withdraw {
+        amount: Amount,
+    },
+    WithdrawFees {
+        amount: Amount,
+    },
+    CreateNewAdmin {
+        employee_id: String,
+    },
+    RecallRevealedTokens {
+        user_id: UserId,
+        amount: Amount,
+    },
+    SeizeAndReissue {
+        user_id: UserId,
+        utxos: Vec<(UtxoId, StealthValueProof)>,
+        case_reference: String,
+    },
+    SetApprovalThreshold {
+        threshold: u32,
+    },
+    RevokeAdmin {
+        admin_account: ComponentAddress,
+        admin_badge_id: NonFungibleId,
+    },
+    RotateAdmin {
+        admin_account: ComponentAddress,
+        admin_badge_id: NonFungibleId,
+    },
+}
+
+impl AdminOperation {
+    /// The role the proposer must hold, matching the role required to call the operation directly
+    pub fn required_role(&self) -> AdminRole {
+        match self {
+            Self::IncreaseSupply { .. } | Self::Withdraw { .. } => AdminRole::Minter,
+            Self::DecreaseSupply { .. } => AdminRole::Burner,
+            Self::WithdrawFees { .. } => AdminRole::FeeManager,
+            Self::RecallRevealedTokens { .. } | Self::SeizeAndReissue { .. } => {
+                AdminRole::Compliance
+            }
+            Self::CreateNewAdmin { .. }
+            | Self::SetApprovalThreshold { .. }
+            | Self::RevokeAdmin { .. }
+            | Self::RotateAdmin { .. } => AdminRole::Super,
+        }
+    }
+}
+
+impl fmt::Display for AdminOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IncreaseSupply { amount } => write!(f, "increase_supply({})", amount),
+            Self::DecreaseSupply { amount } => write!(f, "decrease_supply({})", amount),
+            Self::Withdraw { amount } => write!(f, "withdraw({})", amount),
+            Self::WithdrawFees { amount } => write!(f, "withdraw_fees({})", amount),
+            Self::CreateNewAdmin { employee_id } => write!(f, "create_new_admin({})", employee_id),
+            Self::RecallRevealedTokens { user_id, amount } => {
+                write!(f, "recall_revealed_tokens({}, {})", user_id, amount)
+            }
+            Self::SeizeAndReissue {
+                user_id,
+                case_reference,
+                ..
+            } => write!(f, "seize_and_reissue({}, {})", user_id, case_reference),
+            Self::SetApprovalThreshold { threshold } => {
+                write!(f, "set_approval_threshold({})", threshold)
+            }
+            Self::RevokeAdmin { admin_badge_id, .. } => {
+                write!(f, "revoke_admin({})", admin_badge_id)
+            }
+            Self::RotateAdmin { admin_badge_id, .. } => {
+                write!(f, "rotate_admin({})", admin_badge_id)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Proposal {
+    pub operation: AdminOperation,
+    /// The admin badge that proposed the operation. Its mint allowance is used for `IncreaseSupply`.
+    pub proposer: NonFungibleId,
+    pub approvals: BTreeSet<NonFungibleId>,
+    /// The proposal can no longer be approved from this epoch onwards
+    pub expires_at_epoch: u64,
+}
+
+impl Proposal {
+    pub fn new(operation: AdminOperation, proposer: NonFungibleId, expires_at_epoch: u64) -> Self {
+        let mut approvals = BTreeSet::new();
+        approvals.insert(proposer.clone());
+        Self {
+            operation,
+            proposer,
+            approvals,
+            expires_at_epoch,
+        }
+    }
+
+    pub fn is_expired(&self, current_epoch: u64) -> bool {
+        current_epoch >= self.expires_at_epoch
+    }
+
+    pub fn approve(&mut self, admin_badge: NonFungibleId) -> &mut Self {
+        assert!(
+            self.approvals.insert(admin_badge),
+            "This admin badge has already approved the proposal"
+        );
+        self
+    }
+
+    pub fn num_approvals(&self) -> u32 {
+        self.approvals.len() as u32
+    }
+}
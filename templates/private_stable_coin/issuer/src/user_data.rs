@@ -1,11 +1,14 @@
 // Copyright 2024 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
+use crate::config::FeeSpec;
 use core::fmt;
 use tari_template_lib::component::ComponentManager;
 use tari_template_lib::{types::Amount, types::NonFungibleId};
 
-#[derive(Clone, Debug, Copy, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 #[serde(transparent)]
 pub struct UserId(u64);
 
@@ -31,21 +34,129 @@ pub struct UserData {
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct UserMutableData {
     pub is_blacklisted: bool,
+    /// A frozen user can neither send nor receive tokens until they are unfrozen
+    pub is_frozen: bool,
+    /// The maximum amount that may be exchanged for wrapped tokens in each exchange window
     pub wrapped_exchange_limit: Amount,
+    /// The amount exchanged for wrapped tokens in the current exchange window
+    pub wrapped_exchanged_in_window: Amount,
+    /// The maximum amount of wrapped tokens that may be redeemed for stable tokens in each exchange window
+    pub redemption_limit: Amount,
+    /// The amount of wrapped tokens redeemed in the current exchange window
+    pub redeemed_in_window: Amount,
+    /// The epoch in which the current exchange window started
+    pub exchange_window_start_epoch: u64,
+    /// Replaces the configured transfer fee for this user if set
+    pub transfer_fee_override: Option<FeeSpec>,
+    /// Replaces the configured wrapped exchange fee for this user if set
+    pub wrapped_exchange_fee_override: Option<FeeSpec>,
 }
 
 impl UserMutableData {
+    /// Rejects blacklisted users. Checked against the badge data so that a proof for a badge that has not been recalled
+    /// is still refused.
+    pub fn assert_not_blacklisted(&self, user_id: UserId) {
+        assert!(!self.is_blacklisted, "User {} is blacklisted", user_id);
+    }
+
+    pub fn assert_not_frozen(&self, user_id: UserId) {
+        assert!(!self.is_frozen, "User {} is frozen", user_id);
+    }
+
     pub fn set_wrapped_exchange_limit(&mut self, limit: Amount) -> &mut Self {
         self.wrapped_exchange_limit = limit;
         self
     }
+
+    /// Starts a new exchange window if the current one has elapsed
+    pub fn roll_exchange_window(&mut self, current_epoch: u64, window_epochs: u64) -> &mut Self {
+        if current_epoch
+            >= self
+                .exchange_window_start_epoch
+                .saturating_add(window_epochs)
+        {
+            self.exchange_window_start_epoch = current_epoch;
+            self.wrapped_exchanged_in_window = Amount::ZERO;
+            self.redeemed_in_window = Amount::ZERO;
+        }
+        self
+    }
+
+    pub fn remaining_wrapped_exchange_limit(&self) -> Amount {
+        self.wrapped_exchange_limit
+            .saturating_sub(self.wrapped_exchanged_in_window)
+    }
+
+    pub fn record_wrapped_exchange(&mut self, amount: Amount) -> &mut Self {
+        self.wrapped_exchanged_in_window = self
+            .wrapped_exchanged_in_window
+            .checked_add(amount)
+            .expect("Exchange amount overflowed");
+        self
+    }
+
+    pub fn set_redemption_limit(&mut self, limit: Amount) -> &mut Self {
+        self.redemption_limit = limit;
+        self
+    }
+
+    pub fn remaining_redemption_limit(&self) -> Amount {
+        self.redemption_limit
+            .saturating_sub(self.redeemed_in_window)
+    }
+
+    pub fn record_redemption(&mut self, amount: Amount) -> &mut Self {
+        self.redeemed_in_window = self
+            .redeemed_in_window
+            .checked_add(amount)
+            .expect("Redemption amount overflowed");
+        self
+    }
 }
 
 impl Default for UserMutableData {
     fn default() -> Self {
         Self {
             is_blacklisted: false,
+            is_frozen: false,
             wrapped_exchange_limit: 1000u64.into(),
+            wrapped_exchanged_in_window: Amount::ZERO,
+            redemption_limit: 1000u64.into(),
+            redeemed_in_window: Amount::ZERO,
+            exchange_window_start_epoch: 0,
+            transfer_fee_override: None,
+            wrapped_exchange_fee_override: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "User 0000000000000000001 is blacklisted")]
+    fn test_assert_not_blacklisted() {
+        let user_data = UserMutableData::default();
+        user_data.assert_not_blacklisted(UserId(1));
+
+        let blacklisted = UserMutableData {
+            is_blacklisted: true,
+            ..user_data
+        };
+        blacklisted.assert_not_blacklisted(UserId(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "User 0000000000000000001 is frozen")]
+    fn test_assert_not_frozen() {
+        let user_data = UserMutableData::default();
+        user_data.assert_not_frozen(UserId(1));
+
+        let frozen = UserMutableData {
+            is_frozen: true,
+            ..user_data
+        };
+        frozen.assert_not_frozen(UserId(1));
+    }
+}
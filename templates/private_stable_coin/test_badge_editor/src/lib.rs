@@ -0,0 +1,34 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Test-only template that edits the mutable data of a user badge directly, so that the issuer test suites can
+//! exercise states that the issuer itself never produces, such as a blacklisted badge still held in the user's
+//! account. It is called in the same transaction as an admin badge proof, which satisfies the badge resource's
+//! `update_non_fungible_data` rule. It is not meant to be deployed.
+
+use tari_template_lib::prelude::*;
+
+#[template]
+mod template {
+    use super::*;
+
+    pub struct TestBadgeEditor;
+
+    impl TestBadgeEditor {
+        /// Sets a boolean field in the badge's mutable data, leaving the other fields as they are
+        pub fn set_flag(badge_resource: ResourceAddress, badge_id: NonFungibleId, field: String, value: bool) {
+            let manager = ResourceManager::get(badge_resource);
+            let mut data = manager
+                .get_non_fungible(&badge_id)
+                .get_mutable_data::<tari_bor::Value>();
+            let entry = data
+                .as_map_mut()
+                .expect("Badge mutable data must be a map")
+                .iter_mut()
+                .find(|(key, _)| key.as_text() == Some(field.as_str()))
+                .unwrap_or_else(|| panic!("Badge mutable data has no field {field}"));
+            entry.1 = tari_bor::Value::Bool(value);
+            manager.update_non_fungible_data(badge_id, &data);
+        }
+    }
+}
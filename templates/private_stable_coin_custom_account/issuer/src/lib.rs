@@ -176,11 +176,18 @@ mod stable_coin {
             user_public_key: RistrettoPublicKeyBytes,
             component_address: ComponentAddress,
             vault_id: VaultId,
+            reason_code: ReasonCode,
         ) {
             // TODO: we should fetch the associated component address for the user, however this requires that we know the user
-            if !self
+            if self
                 .deny_list
-                .insert_entry(user_public_key, component_address)
+                .insert(
+                    Some(component_address),
+                    Some(user_public_key),
+                    reason_code,
+                    Consensus::current_epoch(),
+                )
+                .is_none()
             {
                 panic!("User already on deny list");
             }
@@ -200,7 +207,10 @@ mod stable_coin {
 
             emit_event(
                 "add_user_to_deny_list",
-                [("user_public_key", user_public_key.to_string())],
+                [
+                    ("user_public_key", user_public_key.to_string()),
+                    ("reason_code", reason_code.to_string()),
+                ],
             );
         }
 
@@ -209,7 +219,11 @@ mod stable_coin {
             admin_proof: Proof,
             user_public_key: RistrettoPublicKeyBytes,
         ) {
-            let Some(component) = self.deny_list.remove_by_public_key(&user_public_key) else {
+            let Some(component) = self
+                .deny_list
+                .remove_by_public_key(&user_public_key)
+                .and_then(|entry| entry.component)
+            else {
                 panic!("User not found in blacklist");
             };
 
@@ -232,6 +246,20 @@ mod stable_coin {
             );
         }
 
+        /// Returns up to `limit` deny list entries, starting after the given entry ID
+        pub fn list_deny_list(
+            &self,
+            start_after: Option<DenyListEntryId>,
+            limit: u32,
+        ) -> Vec<DenyListEntry> {
+            self.deny_list.list(start_after, limit as usize)
+        }
+
+        /// Returns every deny list entry with its reason code and the epoch it was added, for audits
+        pub fn export_deny_list(&self) -> Vec<DenyListEntry> {
+            self.deny_list.export()
+        }
+
         pub fn create_user_account(
             &mut self,
             admin_proof: Proof,
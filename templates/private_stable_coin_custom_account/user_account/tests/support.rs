@@ -188,7 +188,7 @@ impl UserAccountTest {
                     self.stable_coin_issuer_component,
                     "add_user_to_deny_list",
                     // TODO: right now we need to pass both in
-                    args![Workspace("proof"), pk, account, vault_id],
+                    args![Workspace("proof"), pk, account, vault_id, 0u32],
                 )
                 .drop_all_proofs_in_workspace()
                 .build_and_seal(&self.admin_key),